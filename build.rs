@@ -3,117 +3,200 @@ fn main() {
     build_embedded();
 }
 
+/// Reading the registry files, shared with the `iana-registry` runtime loader
+#[cfg(feature = "embed")]
+#[path = "src/iana_rows.rs"]
+mod iana_rows;
+
+/// Reading dates, shared with `Date`
+#[cfg(feature = "embed")]
+#[path = "src/date_parts.rs"]
+mod date_parts;
+
+/// Splitting reference fields, shared with `Reference::parse`
+#[cfg(feature = "embed")]
+#[path = "src/reference_parts.rs"]
+mod reference_parts;
+
+/// Splitting records into words for the text index, shared with `search_text`
+#[cfg(feature = "embed")]
+#[path = "src/text_tokens.rs"]
+mod text_tokens;
+
+/// Environment variable pointing at a local copy of the IANA XML registry
+#[cfg(feature = "embed")]
+const XML_ENV: &str = "IANA_SERVICES_XML";
+
+/// Environment variable pointing at a local copy of the IANA CSV
+#[cfg(feature = "embed")]
+const CSV_ENV: &str = "IANA_SERVICES_CSV";
+
+/// Snapshot of the IANA CSV shipped with the crate
+#[cfg(all(feature = "embed", not(feature = "fetch")))]
+const VENDORED_CSV: &str = "data/service-names-port-numbers.csv";
+
+/// A copy of the IANA registry in one of its published formats
+#[cfg(feature = "embed")]
+enum RegistryFile {
+    Csv(String),
+    Xml(String),
+}
+
+/// Load the IANA registry.
+///
+/// Sources are tried in order: the XML file named by `IANA_SERVICES_XML`, the CSV
+/// file named by `IANA_SERVICES_CSV`, a fresh download of the CSV from iana.org
+/// (only with the `fetch` feature), and finally the vendored CSV snapshot.
+#[cfg(feature = "embed")]
+fn load_registry() -> RegistryFile {
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    for (var, format) in [
+        (XML_ENV, RegistryFile::Xml as fn(String) -> RegistryFile),
+        (CSV_ENV, RegistryFile::Csv),
+    ] {
+        println!("cargo:rerun-if-env-changed={}", var);
+        if let Some(path) = env::var_os(var) {
+            let path = Path::new(&path);
+            println!("cargo:rerun-if-changed={}", path.display());
+            return format(
+                fs::read_to_string(path).unwrap_or_else(|e| {
+                    panic!("Failed to read {} ({}): {}", var, path.display(), e)
+                }),
+            );
+        }
+    }
+
+    #[cfg(feature = "fetch")]
+    {
+        let url = "https://www.iana.org/assignments/service-names-port-numbers/service-names-port-numbers.csv";
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("iana-services-rust-crate/0.1.0")
+            .build()
+            .expect("Failed to build HTTP client");
+        RegistryFile::Csv(
+            client
+                .get(url)
+                .send()
+                .expect("Failed to fetch IANA services file")
+                .text()
+                .expect("Failed to read response body"),
+        )
+    }
+
+    #[cfg(not(feature = "fetch"))]
+    {
+        let path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(VENDORED_CSV);
+        println!("cargo:rerun-if-changed={}", path.display());
+        RegistryFile::Csv(fs::read_to_string(&path).unwrap_or_else(|e| {
+            panic!(
+                "Failed to read vendored IANA registry ({}): {}. Set {} or {} to a local copy or enable the `fetch` feature.",
+                path.display(),
+                e,
+                CSV_ENV,
+                XML_ENV
+            )
+        }))
+    }
+}
+
+/// Number of TransportProtocol variants
+#[cfg(feature = "embed")]
+const PROTOCOL_COUNT: usize = iana_rows::PROTOCOLS.len();
+
+/// Number of distinct port numbers
+#[cfg(feature = "embed")]
+const PORT_COUNT: usize = u16::MAX as usize + 1;
+
 #[cfg(feature = "embed")]
 fn build_embedded() {
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use std::env;
     use std::fs::File;
     use std::io::{BufWriter, Write};
     use std::path::Path;
     println!("cargo:rerun-if-changed=build.rs");
 
-    // Fetch IANA service names CSV
-    let url = "https://www.iana.org/assignments/service-names-port-numbers/service-names-port-numbers.csv";
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("iana-services-rust-crate/0.1.0")
-        .build()
-        .expect("Failed to build HTTP client");
-    let response = client
-        .get(url)
-        .send()
-        .expect("Failed to fetch IANA services file")
-        .text()
-        .expect("Failed to read response body");
-
-    // Parse CSV
-    let mut csv_reader = csv::Reader::from_reader(response.as_bytes());
-
-    // Group records by port and by name
-    let mut by_port: HashMap<u16, Vec<ServiceEntry>> = HashMap::new();
-    let mut by_name: HashMap<String, Vec<ServiceEntry>> = HashMap::new();
-
-    for result in csv_reader.records() {
-        let record = result.expect("Failed to parse CSV record");
-
-        // Extract fields
-        let service_name = record.get(0).unwrap_or("").trim().to_string();
-        let port_str = record.get(1).unwrap_or("").trim();
-        let protocol_str = record.get(2).unwrap_or("").trim().to_lowercase();
-        let description = record.get(3).unwrap_or("").trim().to_string();
-        let assignee = record.get(4).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let contact = record.get(5).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let registration_date = record.get(6).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let modification_date = record.get(7).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let reference = record.get(8).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let service_code = record.get(9).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let unauthorized_use = record.get(10).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
-        let assignment_notes = record.get(11).and_then(|s| {
-            if s.trim().is_empty() {
-                None
-            } else {
-                Some(s.trim().to_string())
-            }
-        });
+    // Normalize the BOM and line endings so the generated code only depends on
+    // the registry data and not on where it came from
+    let (rows, updated, people) = match load_registry() {
+        RegistryFile::Csv(data) => {
+            let data = data.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+            let rows: Vec<iana_rows::Row> = iana_rows::csv_rows(data.as_bytes())
+                .map(|row| row.expect("Failed to parse CSV record").1)
+                .collect();
+            (rows, None, Vec::new())
+        }
+        RegistryFile::Xml(data) => {
+            let data = data.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+            let registry = iana_rows::parse_xml(&data).expect("Failed to parse XML registry");
+            let rows = registry.rows.into_iter().map(|(_, row)| row).collect();
+            (rows, registry.updated, registry.people)
+        }
+    };
+
+    let mut all_entries: Vec<ServiceEntry> = Vec::new();
+
+    // Per-port flags, indexed by protocol key: listed as unassigned, and taken by
+    // any other (assigned or reserved) row
+    let mut unassigned = vec![[false; PROTOCOL_COUNT]; PORT_COUNT];
+    let mut taken = vec![[false; PROTOCOL_COUNT]; PORT_COUNT];
+
+    for row in rows {
+        // Rows without a port number, with a malformed one or with an unknown
+        // protocol are skipped
+        let Ok(Some(assignment)) = row.assignment() else {
+            continue;
+        };
+        let is_unassigned = row.is_unassigned();
+        let iana_rows::Row {
+            name: service_name,
+            description,
+            assignee,
+            contact,
+            registration_date,
+            modification_date,
+            reference,
+            service_code,
+            unauthorized_use,
+            assignment_notes,
+            ..
+        } = row;
+        let (port, port_end) = (assignment.port, assignment.port_end);
 
-        // Skip entries without port numbers or with port ranges
-        let port: u16 = match port_str.parse() {
-            Ok(p) => p,
-            Err(_) => continue,
+        // Rows without a protocol apply to all of them
+        let protocol_keys = match assignment.protocol {
+            Some(key) => key..key + 1,
+            None => 0..PROTOCOL_COUNT,
+        };
+        let flags = if is_unassigned {
+            &mut unassigned
+        } else {
+            &mut taken
         };
+        for port in port..=port_end {
+            for key in protocol_keys.clone() {
+                flags[port as usize][key] = true;
+            }
+        }
+
+        // Unassigned rows aren't services, they only feed the unassigned port index
+        if is_unassigned {
+            continue;
+        }
 
-        // Parse protocol
-        let protocol = match protocol_str.as_str() {
-            "tcp" => "TransportProtocol::Tcp",
-            "udp" => "TransportProtocol::Udp",
-            _ => continue, // Skip unknown protocols
+        let Some(protocol_key) = assignment.protocol else {
+            continue;
         };
 
         let entry = ServiceEntry {
-            name: service_name.clone(),
+            name: service_name,
             port,
-            protocol: protocol.to_string(),
+            port_end,
+            protocol: protocol_code(protocol_key),
+            protocol_key: protocol_key as u8,
             description,
             assignee,
             contact,
@@ -125,29 +208,87 @@ fn build_embedded() {
             assignment_notes,
         };
 
-        // Add to port map
-        by_port.entry(port).or_default().push(entry.clone());
+        all_entries.push(entry);
+    }
+
+    // Single-port records come first, sorted by (port, protocol, name), followed
+    // by ranged records starting at RANGE_RECORDS_START
+    all_entries.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    let range_records_start = all_entries.iter().take_while(|e| !e.is_range()).count();
+
+    // Derive the port index from the sorted records, so each port maps to the
+    // contiguous run of records carrying it
+    let mut port_ranges: BTreeMap<u16, (usize, usize)> = BTreeMap::new();
+    let mut port_protocol_ranges: BTreeMap<(u16, u8), (usize, usize)> = BTreeMap::new();
+    for (idx, entry) in all_entries[..range_records_start].iter().enumerate() {
+        port_ranges
+            .entry(entry.port)
+            .and_modify(|(_, end)| *end = idx + 1)
+            .or_insert((idx, idx + 1));
+        port_protocol_ranges
+            .entry((entry.port, entry.protocol_key))
+            .and_modify(|(_, end)| *end = idx + 1)
+            .or_insert((idx, idx + 1));
+    }
+
+    let mut name_ranges: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, entry) in all_entries.iter().enumerate() {
+        if !entry.name.is_empty() {
+            // Service names are case-insensitive, so index them lowercased
+            name_ranges
+                .entry(entry.name.to_ascii_lowercase())
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    let mut name_protocol_ranges: BTreeMap<String, [Vec<usize>; PROTOCOL_COUNT]> = BTreeMap::new();
+    for (name, indices) in &name_ranges {
+        let by_protocol = name_protocol_ranges.entry(name.clone()).or_default();
+        for &idx in indices {
+            by_protocol[all_entries[idx].protocol_key as usize].push(idx);
+        }
+    }
 
-        // Add to name map (only if name is not empty)
-        if !service_name.is_empty() {
-            by_name.entry(service_name).or_default().push(entry);
+    let mut rfc_ranges: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    for (idx, entry) in all_entries.iter().enumerate() {
+        let parts = reference_parts::parts(entry.reference.as_deref().unwrap_or(""));
+        for number in parts.filter_map(|part| match part {
+            reference_parts::Part::Bracketed(id) => reference_parts::rfc_number(id),
+            reference_parts::Part::Text(_) => None,
+        }) {
+            let indices = rfc_ranges.entry(number).or_default();
+            // A record may reference the same RFC more than once
+            if indices.last() != Some(&idx) {
+                indices.push(idx);
+            }
         }
     }
 
+    verify_indices(
+        &all_entries,
+        range_records_start,
+        &port_ranges,
+        &port_protocol_ranges,
+        &name_ranges,
+        &name_protocol_ranges,
+    );
+
     // Generate code
     let path = Path::new(&env::var("OUT_DIR").unwrap()).join("codegen.rs");
     let mut file = BufWriter::new(File::create(&path).unwrap());
 
     // Generate service records as static data
     writeln!(&mut file, "static SERVICE_RECORDS: &[ServiceRecord] = &[").unwrap();
-    let mut all_entries: Vec<_> = by_port.values().flat_map(|v| v.iter()).collect();
-    all_entries.sort_by_key(|e| (e.port, &e.name));
 
     for entry in &all_entries {
         writeln!(&mut file, "    ServiceRecord {{").unwrap();
         writeln!(&mut file, "        name: {:?},", entry.name).unwrap();
         writeln!(&mut file, "        port: {},", entry.port).unwrap();
+        writeln!(&mut file, "        port_end: {},", entry.port_end).unwrap();
         writeln!(&mut file, "        protocol: {},", entry.protocol).unwrap();
+        // The IANA registry doesn't list aliases
+        writeln!(&mut file, "        aliases: &[],").unwrap();
 
         if cfg!(feature = "optional-info") {
             writeln!(&mut file, "        #[cfg(feature = \"optional-info\")]").unwrap();
@@ -170,14 +311,14 @@ fn build_embedded() {
             writeln!(
                 &mut file,
                 "        registration_date: {},",
-                option_to_code(&entry.registration_date)
+                date_to_code(&entry.registration_date)
             )
             .unwrap();
             writeln!(&mut file, "        #[cfg(feature = \"optional-info\")]").unwrap();
             writeln!(
                 &mut file,
                 "        modification_date: {},",
-                option_to_code(&entry.modification_date)
+                date_to_code(&entry.modification_date)
             )
             .unwrap();
             writeln!(&mut file, "        #[cfg(feature = \"optional-info\")]").unwrap();
@@ -214,18 +355,66 @@ fn build_embedded() {
     }
     writeln!(&mut file, "];").unwrap();
     writeln!(&mut file).unwrap();
+    writeln!(
+        &mut file,
+        "static RANGE_RECORDS_START: usize = {};",
+        range_records_start
+    )
+    .unwrap();
+    writeln!(&mut file).unwrap();
+    writeln!(
+        &mut file,
+        "static REGISTRY_UPDATED: Option<Date> = {};",
+        date_to_code(&updated)
+    )
+    .unwrap();
+    writeln!(&mut file).unwrap();
+
+    // Generate the people table, sorted by id for binary search
+    if cfg!(feature = "optional-info") {
+        writeln!(&mut file, "#[cfg(feature = \"optional-info\")]").unwrap();
+        writeln!(&mut file, "static PEOPLE: &[EmbeddedPerson] = &[").unwrap();
+        for person in &people {
+            writeln!(
+                &mut file,
+                "    EmbeddedPerson {{ id: {:?}, name: {:?}, org: {}, uri: {} }},",
+                person.id,
+                person.name,
+                option_to_code(&person.org),
+                option_to_code(&person.uri)
+            )
+            .unwrap();
+        }
+        writeln!(&mut file, "];").unwrap();
+        writeln!(&mut file).unwrap();
+    }
+
+    // Generate the unassigned port ranges for each protocol, in key order
+    writeln!(
+        &mut file,
+        "static UNASSIGNED: [&[(u16, u16)]; {}] = [",
+        PROTOCOL_COUNT
+    )
+    .unwrap();
+    for key in 0..PROTOCOL_COUNT {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for port in 0..PORT_COUNT {
+            if !unassigned[port][key] || taken[port][key] {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == port => *end = port,
+                _ => ranges.push((port, port)),
+            }
+        }
+        writeln!(&mut file, "    &{:?},", ranges).unwrap();
+    }
+    writeln!(&mut file, "];").unwrap();
+    writeln!(&mut file).unwrap();
 
     // Generate PHF map for port lookup
     let mut port_map = phf_codegen::Map::new();
-    let mut port_ranges: HashMap<u16, (usize, usize)> = HashMap::new();
     let mut port_values: Vec<String> = Vec::new();
-    let mut offset = 0;
-
-    for (port, entries) in &by_port {
-        let count = entries.len();
-        port_ranges.insert(*port, (offset, offset + count));
-        offset += count;
-    }
 
     // Collect formatted strings first to ensure they live long enough
     for (start, end) in port_ranges.values() {
@@ -244,17 +433,27 @@ fn build_embedded() {
     .unwrap();
     writeln!(&mut file).unwrap();
 
-    // Generate name lookup data only if lookup-by-name feature is enabled
-    if cfg!(feature = "lookup-by-name") {
-        // Generate static arrays for name lookup indices
-        let mut name_ranges: HashMap<String, Vec<usize>> = HashMap::new();
+    // Generate PHF map for (port, protocol) lookup
+    let mut port_protocol_map = phf_codegen::Map::new();
+    let port_protocol_values: Vec<String> = port_protocol_ranges
+        .values()
+        .map(|(start, end)| format!("({}, {})", start, end))
+        .collect();
 
-        for (idx, entry) in all_entries.iter().enumerate() {
-            if !entry.name.is_empty() {
-                name_ranges.entry(entry.name.clone()).or_default().push(idx);
-            }
-        }
+    for (key, value) in port_protocol_ranges.keys().zip(port_protocol_values.iter()) {
+        port_protocol_map.entry(*key, value);
+    }
 
+    writeln!(
+        &mut file,
+        "static BY_PORT_PROTOCOL: phf::Map<(u16, u8), (usize, usize)> = {};",
+        port_protocol_map.build()
+    )
+    .unwrap();
+    writeln!(&mut file).unwrap();
+
+    // Generate name lookup data only if lookup-by-name feature is enabled
+    if cfg!(feature = "lookup-by-name") {
         // Generate static arrays for each service name's indices
         for (idx, (_name, indices)) in name_ranges.iter().enumerate() {
             writeln!(&mut file, "#[cfg(feature = \"lookup-by-name\")]").unwrap();
@@ -267,6 +466,16 @@ fn build_embedded() {
         }
         writeln!(&mut file).unwrap();
 
+        // Lookups lowercase the name into a buffer of this size
+        writeln!(&mut file, "#[cfg(feature = \"lookup-by-name\")]").unwrap();
+        writeln!(
+            &mut file,
+            "const MAX_NAME_LEN: usize = {};",
+            name_ranges.keys().map(String::len).max().unwrap_or(0)
+        )
+        .unwrap();
+        writeln!(&mut file).unwrap();
+
         // Generate PHF map for name lookup
         let mut name_map = phf_codegen::Map::new();
         let name_values: Vec<String> = (0..name_ranges.len())
@@ -284,6 +493,212 @@ fn build_embedded() {
             name_map.build()
         )
         .unwrap();
+        writeln!(&mut file).unwrap();
+
+        // Generate PHF map for (name, protocol) lookup: each name maps to its
+        // indices split by protocol key
+        let mut name_protocol_map = phf_codegen::Map::new();
+        let name_protocol_values: Vec<String> = name_protocol_ranges
+            .values()
+            .map(|by_protocol| {
+                let slices: Vec<String> = by_protocol
+                    .iter()
+                    .map(|indices| format!("&{:?}", indices))
+                    .collect();
+                format!("[{}]", slices.join(", "))
+            })
+            .collect();
+
+        for (name, value) in name_protocol_ranges.keys().zip(name_protocol_values.iter()) {
+            name_protocol_map.entry(name.as_str(), value);
+        }
+
+        writeln!(&mut file, "#[cfg(feature = \"lookup-by-name\")]").unwrap();
+        writeln!(
+            &mut file,
+            "static BY_NAME_PROTOCOL: phf::Map<&'static str, [&'static [usize]; {}]> = {};",
+            PROTOCOL_COUNT,
+            name_protocol_map.build()
+        )
+        .unwrap();
+        writeln!(&mut file).unwrap();
+
+        // Generate the name table for searches: lowercased names in sorted order,
+        // each with the registry's spelling
+        writeln!(&mut file, "#[cfg(feature = \"lookup-by-name\")]").unwrap();
+        writeln!(&mut file, "static SORTED_NAMES: &[(&str, &str)] = &[").unwrap();
+        for (name, indices) in &name_ranges {
+            writeln!(
+                &mut file,
+                "    ({:?}, {:?}),",
+                name, all_entries[indices[0]].name
+            )
+            .unwrap();
+        }
+        writeln!(&mut file, "];").unwrap();
+    }
+
+    // Generate the RFC reverse index only if the references are embedded
+    if cfg!(feature = "optional-info") {
+        for (idx, indices) in rfc_ranges.values().enumerate() {
+            writeln!(&mut file, "#[cfg(feature = \"optional-info\")]").unwrap();
+            writeln!(
+                &mut file,
+                "static RFC_INDICES_{}: &[usize] = &{:?};",
+                idx, indices
+            )
+            .unwrap();
+        }
+        writeln!(&mut file).unwrap();
+
+        let mut rfc_map = phf_codegen::Map::new();
+        let rfc_values: Vec<String> = (0..rfc_ranges.len())
+            .map(|idx| format!("RFC_INDICES_{}", idx))
+            .collect();
+
+        for (number, value) in rfc_ranges.keys().zip(rfc_values.iter()) {
+            rfc_map.entry(*number, value);
+        }
+
+        writeln!(&mut file, "#[cfg(feature = \"optional-info\")]").unwrap();
+        writeln!(
+            &mut file,
+            "static BY_RFC: phf::Map<u32, &'static [usize]> = {};",
+            rfc_map.build()
+        )
+        .unwrap();
+    }
+
+    // Generate the inverted text index only if the searchable fields are embedded
+    if cfg!(feature = "optional-info") {
+        let mut words: BTreeMap<String, BTreeMap<usize, u8>> = BTreeMap::new();
+        for (idx, entry) in all_entries.iter().enumerate() {
+            let record_words = text_tokens::record_words(
+                &entry.name,
+                &entry.description,
+                entry.assignee.as_deref(),
+                entry.assignment_notes.as_deref(),
+            );
+            for (word, fields) in record_words {
+                words.entry(word).or_default().insert(idx, fields);
+            }
+        }
+
+        writeln!(&mut file, "#[cfg(feature = \"optional-info\")]").unwrap();
+        writeln!(&mut file, "static TEXT_INDEX: &[(&str, &[(u32, u8)])] = &[").unwrap();
+        for (word, postings) in &words {
+            let postings: Vec<(usize, u8)> = postings.iter().map(|(&i, &f)| (i, f)).collect();
+            writeln!(&mut file, "    ({:?}, &{:?}),", word, postings).unwrap();
+        }
+        writeln!(&mut file, "];").unwrap();
+    }
+
+    /// Check that every record reachable through the generated indices carries the
+    /// key it is indexed under, and that every record is reachable.
+    fn verify_indices(
+        entries: &[ServiceEntry],
+        range_records_start: usize,
+        port_ranges: &BTreeMap<u16, (usize, usize)>,
+        port_protocol_ranges: &BTreeMap<(u16, u8), (usize, usize)>,
+        name_ranges: &BTreeMap<String, Vec<usize>>,
+        name_protocol_ranges: &BTreeMap<String, [Vec<usize>; PROTOCOL_COUNT]>,
+    ) {
+        let mut indexed = 0;
+        for (port, (start, end)) in port_ranges {
+            for entry in &entries[*start..*end] {
+                assert!(
+                    entry.port == *port && !entry.is_range(),
+                    "BY_PORT entry for port {} contains {} on port {}",
+                    port,
+                    entry.name,
+                    entry.port
+                );
+            }
+            indexed += end - start;
+        }
+        assert_eq!(
+            indexed, range_records_start,
+            "BY_PORT doesn't cover every single-port record exactly once"
+        );
+
+        let mut indexed = 0;
+        for ((port, protocol_key), (start, end)) in port_protocol_ranges {
+            for entry in &entries[*start..*end] {
+                assert!(
+                    entry.port == *port && entry.protocol_key == *protocol_key && !entry.is_range(),
+                    "BY_PORT_PROTOCOL entry for port {} contains {} on port {} ({})",
+                    port,
+                    entry.name,
+                    entry.port,
+                    entry.protocol
+                );
+            }
+            indexed += end - start;
+        }
+        assert_eq!(
+            indexed, range_records_start,
+            "BY_PORT_PROTOCOL doesn't cover every single-port record exactly once"
+        );
+
+        let mut indexed = 0;
+        for (name, indices) in name_ranges {
+            for &idx in indices {
+                assert_eq!(
+                    &entries[idx].name.to_ascii_lowercase(),
+                    name,
+                    "BY_NAME entry for {} points at the wrong record",
+                    name
+                );
+            }
+            indexed += indices.len();
+        }
+        assert_eq!(
+            indexed,
+            entries.iter().filter(|e| !e.name.is_empty()).count(),
+            "BY_NAME doesn't cover every named record exactly once"
+        );
+
+        let mut indexed = 0;
+        for (name, by_protocol) in name_protocol_ranges {
+            for (protocol_key, indices) in by_protocol.iter().enumerate() {
+                for &idx in indices {
+                    let entry = &entries[idx];
+                    assert!(
+                        &entry.name.to_ascii_lowercase() == name
+                            && entry.protocol_key as usize == protocol_key,
+                        "BY_NAME_PROTOCOL entry for {} contains {} ({})",
+                        name,
+                        entry.name,
+                        entry.protocol
+                    );
+                }
+                indexed += indices.len();
+            }
+        }
+        assert_eq!(
+            indexed,
+            entries.iter().filter(|e| !e.name.is_empty()).count(),
+            "BY_NAME_PROTOCOL doesn't cover every named record exactly once"
+        );
+    }
+
+    /// Code for the protocol with the given key (`tcp` is `TransportProtocol::Tcp`)
+    fn protocol_code(key: usize) -> String {
+        let name = iana_rows::PROTOCOLS[key];
+        format!(
+            "TransportProtocol::{}{}",
+            name[..1].to_ascii_uppercase(),
+            &name[1..]
+        )
+    }
+
+    /// Turn a `YYYY-MM-DD` date into a `Date` constructor. Malformed dates are
+    /// left out, like in `Date::from_str`.
+    fn date_to_code(date: &Option<String>) -> String {
+        match date.as_deref().and_then(date_parts::parse) {
+            Some((year, month, day)) => format!("Date::new({}, {}, {})", year, month, day),
+            None => "None".to_string(),
+        }
     }
 
     fn option_to_code(opt: &Option<String>) -> String {
@@ -297,7 +712,9 @@ fn build_embedded() {
     struct ServiceEntry {
         name: String,
         port: u16,
+        port_end: u16,
         protocol: String,
+        protocol_key: u8,
         description: String,
         assignee: Option<String>,
         contact: Option<String>,
@@ -308,4 +725,20 @@ fn build_embedded() {
         unauthorized_use: Option<String>,
         assignment_notes: Option<String>,
     }
+
+    impl ServiceEntry {
+        fn is_range(&self) -> bool {
+            self.port != self.port_end
+        }
+
+        fn sort_key(&self) -> (bool, u16, u16, u8, &str) {
+            (
+                self.is_range(),
+                self.port,
+                self.port_end,
+                self.protocol_key,
+                &self.name,
+            )
+        }
+    }
 }
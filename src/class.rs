@@ -0,0 +1,132 @@
+use crate::{ServiceRecord, TransportProtocol};
+use std::ops::RangeInclusive;
+
+/// Port number ranges defined by RFC 6335 §6
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortClass {
+    /// System Ports (0-1023), assigned by IETF process
+    System,
+    /// User Ports (1024-49151), assigned by IANA
+    User,
+    /// Dynamic and/or Private Ports (49152-65535), never assigned
+    Dynamic,
+}
+
+impl PortClass {
+    /// The ports belonging to this class
+    pub fn range(self) -> RangeInclusive<u16> {
+        match self {
+            PortClass::System => 0..=1023,
+            PortClass::User => 1024..=49151,
+            PortClass::Dynamic => 49152..=65535,
+        }
+    }
+}
+
+/// Registration status of a port for a transport protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PortStatus {
+    /// At least one named service is registered on the port
+    Assigned,
+    /// The port is reserved without being assigned to a named service
+    Reserved,
+    /// IANA lists the port as unassigned (only known with the `embed` feature,
+    /// see [`is_unassigned`](crate::is_unassigned))
+    Unassigned,
+    /// Nothing is registered on the port, and IANA doesn't list it as unassigned
+    /// either, e.g. because the port is only assigned over other protocols. Without
+    /// `embed`, every port without records outside the dynamic range has this status.
+    Unregistered,
+    /// Nothing is registered on the port, which is in the Dynamic and/or Private
+    /// range (RFC 6335 §6) that IANA never assigns
+    Dynamic,
+}
+
+impl PortStatus {
+    fn from_records<'a, I: IntoIterator<Item = &'a ServiceRecord>>(records: I) -> Option<Self> {
+        let mut status = None;
+
+        for record in records {
+            if record.is_assigned() {
+                return Some(PortStatus::Assigned);
+            }
+            status = Some(PortStatus::Reserved);
+        }
+
+        status
+    }
+}
+
+/// Classify a port according to RFC 6335
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{classify, PortClass};
+///
+/// assert_eq!(classify(443), PortClass::System);
+/// assert_eq!(classify(8080), PortClass::User);
+/// assert_eq!(classify(51000), PortClass::Dynamic);
+/// ```
+pub fn classify(port: u16) -> PortClass {
+    match port {
+        0..=1023 => PortClass::System,
+        1024..=49151 => PortClass::User,
+        _ => PortClass::Dynamic,
+    }
+}
+
+/// Look up the registration status of a port for a transport protocol
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{port_status, PortStatus, TransportProtocol};
+///
+/// let port = 8123;
+/// if matches!(
+///     port_status(port, TransportProtocol::Tcp),
+///     PortStatus::Unassigned | PortStatus::Unregistered
+/// ) {
+///     println!("Traffic on unregistered port {port}");
+/// }
+/// assert_eq!(port_status(51000, TransportProtocol::Tcp), PortStatus::Dynamic);
+/// ```
+pub fn port_status(port: u16, protocol: TransportProtocol) -> PortStatus {
+    #[cfg(feature = "embed")]
+    let status = PortStatus::from_records(crate::embedded::lookup_iter(port, protocol));
+    #[cfg(not(feature = "embed"))]
+    let status = PortStatus::from_records(crate::lookup(port, protocol).iter().flatten());
+
+    if let Some(status) = status {
+        return status;
+    }
+    if classify(port) == PortClass::Dynamic {
+        return PortStatus::Dynamic;
+    }
+
+    #[cfg(feature = "embed")]
+    if crate::embedded::is_unassigned(port, protocol) {
+        return PortStatus::Unassigned;
+    }
+
+    PortStatus::Unregistered
+}
+
+impl ServiceRecord {
+    /// The RFC 6335 class of the (first) port of this record
+    pub fn port_class(&self) -> PortClass {
+        classify(self.port)
+    }
+
+    /// Whether this record assigns the port to a named service
+    pub fn is_assigned(&self) -> bool {
+        !self.name.is_empty()
+    }
+
+    /// Whether this record reserves the port without naming a service
+    /// (e.g. port 0, or the RFC 3692 experimental ports)
+    pub fn is_reserved(&self) -> bool {
+        self.name.is_empty()
+    }
+}
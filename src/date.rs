@@ -0,0 +1,156 @@
+use crate::date_parts;
+use std::fmt;
+use std::str::FromStr;
+
+/// A calendar date, as used by the registry (`YYYY-MM-DD`)
+///
+/// Dates order chronologically, so they can be compared directly.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::Date;
+///
+/// let date: Date = "2017-05-18".parse().unwrap();
+/// assert_eq!(date, Date::new(2017, 5, 18).unwrap());
+/// assert!(date < Date::new(2020, 1, 1).unwrap());
+/// assert_eq!(date.to_string(), "2017-05-18");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    // Field order matters for the derived Ord
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, or `None` if the month or day is out of range
+    pub const fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        if !date_parts::is_valid(year, month, day) {
+            return None;
+        }
+
+        Some(Self { year, month, day })
+    }
+
+    /// Year
+    pub fn year(self) -> u16 {
+        self.year
+    }
+
+    /// Month (1-12)
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month (starting at 1)
+    pub fn day(self) -> u8 {
+        self.day
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    /// Parse a `YYYY-MM-DD` date
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, month, day) = date_parts::parse(s).ok_or_else(|| ParseDateError {
+            date: s.to_string(),
+        })?;
+
+        Ok(Self { year, month, day })
+    }
+}
+
+/// Error returned when parsing a [`Date`] that isn't a valid `YYYY-MM-DD` date
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateError {
+    date: String,
+}
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid date `{}`: expected YYYY-MM-DD", self.date)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        date.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "optional-info")]
+impl crate::ServiceRecord {
+    /// When the record last changed: its modification date, or its registration date
+    /// if it was never modified
+    pub fn last_changed(&self) -> Option<Date> {
+        self.modification_date.or(self.registration_date)
+    }
+}
+
+/// Iterate over the services registered or modified on or after a date
+///
+/// Records are matched by [`ServiceRecord::last_changed`]; records without any date
+/// are never included.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{services_modified_since, Date};
+///
+/// let since = Date::new(2024, 1, 1).unwrap();
+/// for service in services_modified_since(since) {
+///     println!("{} changed on {:?}", service.name, service.last_changed());
+/// }
+/// ```
+#[cfg(all(feature = "optional-info", feature = "embed"))]
+pub fn services_modified_since(date: Date) -> impl Iterator<Item = &'static crate::ServiceRecord> {
+    crate::embedded::records()
+        .iter()
+        .filter(move |record| record.last_changed().is_some_and(|changed| changed >= date))
+}
+
+/// Iterate over the services registered or modified on or after a date
+///
+/// Records are matched by [`ServiceRecord::last_changed`]; records without any date
+/// are never included.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{services_modified_since, Date};
+///
+/// let since = Date::new(2024, 1, 1).unwrap();
+/// for service in services_modified_since(since) {
+///     println!("{} changed on {:?}", service.name, service.last_changed());
+/// }
+/// ```
+#[cfg(all(feature = "optional-info", not(feature = "embed")))]
+pub fn services_modified_since(date: Date) -> impl Iterator<Item = crate::ServiceRecord> {
+    let registry = crate::runtime::registry();
+    let services: Vec<_> = registry
+        .iter()
+        .filter(|record| record.last_changed().is_some_and(|changed| changed >= date))
+        .cloned()
+        .collect();
+    services.into_iter()
+}
@@ -0,0 +1,37 @@
+//! Reading `YYYY-MM-DD` dates
+//!
+//! This file is shared by build.rs (through `#[path]`) and `Date`, so the dates
+//! embedded by `embed` are read the same way as dates parsed at runtime. It can't use
+//! anything else from the crate.
+
+/// Read a `YYYY-MM-DD` date into its year, month and day, if it's a valid date
+pub(crate) fn parse(date: &str) -> Option<(u16, u8, u8)> {
+    let mut parts = date.trim().split('-');
+    let (Some(year), Some(month), Some(day), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+
+    let (year, month, day) = (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    is_valid(year, month, day).then_some((year, month, day))
+}
+
+/// Whether the month is in range and the day exists in that month
+pub(crate) const fn is_valid(year: u16, month: u8, day: u8) -> bool {
+    month != 0 && month <= 12 && day != 0 && day <= days_in_month(year, month)
+}
+
+const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+/// Errors returned by the fallible lookup and loading functions
+#[derive(Debug)]
+pub enum Error {
+    /// The services file couldn't be read
+    Io(std::io::Error),
+    /// The services file couldn't be parsed
+    Parse {
+        /// Line number (starting at 1) where parsing failed
+        line: usize,
+        /// What went wrong
+        message: String,
+    },
+    /// No service matched the lookup
+    NotFound,
+}
+
+impl Error {
+    /// Copy the error, so a failure to load the system services file can be cached
+    #[cfg(not(feature = "embed"))]
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            Error::Io(e) => Error::Io(std::io::Error::new(e.kind(), e.to_string())),
+            Error::Parse { line, message } => Error::Parse {
+                line: *line,
+                message: message.clone(),
+            },
+            Error::NotFound => Error::NotFound,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "failed to read services file: {}", e),
+            Error::Parse { line, message } => {
+                write!(
+                    f,
+                    "failed to parse services file at line {}: {}",
+                    line, message
+                )
+            }
+            Error::NotFound => write!(f, "service not found"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// A malformed line that was skipped while loading a services file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Line number (starting at 1)
+    pub line: usize,
+    /// Why the line was skipped
+    pub message: String,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
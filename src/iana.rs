@@ -0,0 +1,149 @@
+//! Parsers for the IANA registry files, for loading them at runtime
+
+#[cfg(feature = "optional-info")]
+use crate::Person;
+use crate::iana_rows::{self, Assignment, Row};
+use crate::{Date, Error, OwnedServiceRecord, ParseWarning, TransportProtocol};
+use std::io::Read;
+
+/// Everything loaded from a registry file
+#[derive(Default)]
+pub(crate) struct Parsed {
+    pub records: Vec<OwnedServiceRecord>,
+    /// Date the registry was last updated (only in the XML format)
+    pub updated: Option<Date>,
+    /// People referenced by the records, sorted by id (only in the XML format)
+    #[cfg(feature = "optional-info")]
+    pub people: Vec<Person>,
+}
+
+impl Row {
+    /// Convert the row into a record, the same way the build script does for `embed`
+    ///
+    /// Unassigned rows and rows without a port or protocol aren't services and are
+    /// skipped. Unknown protocols are reported as warnings, and so are malformed dates,
+    /// which are left out of the record.
+    fn into_record(
+        self,
+        line: usize,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Option<OwnedServiceRecord> {
+        let mut warn = |message: String| warnings.push(ParseWarning { line, message });
+
+        if self.is_unassigned() {
+            return None;
+        }
+
+        let Assignment {
+            port,
+            port_end,
+            protocol,
+        } = match self.assignment() {
+            Ok(assignment) => assignment?,
+            Err(message) => {
+                warn(message);
+                return None;
+            }
+        };
+        // Rows without a protocol (e.g. reserved ranges) aren't services
+        let protocol: TransportProtocol = iana_rows::PROTOCOLS[protocol?].parse().ok()?;
+
+        #[cfg(feature = "optional-info")]
+        let (registration_date, modification_date) = (
+            parse_date(self.registration_date, &mut warn),
+            parse_date(self.modification_date, &mut warn),
+        );
+
+        Some(OwnedServiceRecord {
+            name: self.name,
+            port,
+            port_end,
+            protocol,
+            aliases: Vec::new(),
+            #[cfg(feature = "optional-info")]
+            description: self.description,
+            #[cfg(feature = "optional-info")]
+            assignee: self.assignee,
+            #[cfg(feature = "optional-info")]
+            contact: self.contact,
+            #[cfg(feature = "optional-info")]
+            registration_date,
+            #[cfg(feature = "optional-info")]
+            modification_date,
+            #[cfg(feature = "optional-info")]
+            reference: self.reference,
+            #[cfg(feature = "optional-info")]
+            service_code: self.service_code,
+            #[cfg(feature = "optional-info")]
+            unauthorized_use: self.unauthorized_use,
+            #[cfg(feature = "optional-info")]
+            assignment_notes: self.assignment_notes,
+        })
+    }
+}
+
+#[cfg(feature = "optional-info")]
+fn parse_date(date: Option<String>, warn: &mut impl FnMut(String)) -> Option<Date> {
+    match date?.parse() {
+        Ok(date) => Some(date),
+        Err(e) => {
+            warn(e.to_string());
+            None
+        }
+    }
+}
+
+/// Parse `service-names-port-numbers.csv`
+pub(crate) fn parse_csv<R: Read>(
+    reader: R,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Parsed, Error> {
+    let mut parsed = Parsed::default();
+
+    for result in iana_rows::csv_rows(reader) {
+        let (line, row) = result.map_err(|e| {
+            let line = e.position().map_or(0, |p| p.line() as usize);
+            let message = e.to_string();
+            match e.into_kind() {
+                csv::ErrorKind::Io(e) => Error::Io(e),
+                _ => Error::Parse { line, message },
+            }
+        })?;
+
+        parsed.records.extend(row.into_record(line, warnings));
+    }
+
+    Ok(parsed)
+}
+
+/// Parse `service-names-port-numbers.xml`, as shipped by e.g. iana-etc
+pub(crate) fn parse_xml(contents: &str, warnings: &mut Vec<ParseWarning>) -> Result<Parsed, Error> {
+    let registry = iana_rows::parse_xml(contents).map_err(|e| Error::Parse {
+        line: e.pos().row as usize,
+        message: e.to_string(),
+    })?;
+
+    let mut parsed = Parsed {
+        updated: registry.updated.and_then(|updated| updated.parse().ok()),
+        ..Default::default()
+    };
+    for (line, row) in registry.rows {
+        parsed.records.extend(row.into_record(line, warnings));
+    }
+
+    #[cfg(feature = "optional-info")]
+    {
+        parsed.people = registry
+            .people
+            .into_iter()
+            .map(|person| Person {
+                id: person.id,
+                name: person.name,
+                org: person.org,
+                uri: person.uri,
+            })
+            .collect();
+    }
+
+    Ok(parsed)
+}
@@ -0,0 +1,235 @@
+//! Reading the rows of the IANA registry files
+//!
+//! This file is shared by build.rs (through `#[path]`) and the `iana-registry`
+//! loader, so the embedded registry and the one loaded at runtime are read the same
+//! way. It can't use anything else from the crate.
+
+use std::io::Read;
+
+/// Transport protocols of the registry, lowercased and in the declaration order of
+/// `TransportProtocol`, so a protocol's position is its key in the generated indices
+pub(crate) const PROTOCOLS: [&str; 4] = ["tcp", "udp", "sctp", "dccp"];
+
+/// A row of the IANA registry, as found in both the CSV and XML formats
+#[derive(Default)]
+#[cfg_attr(not(feature = "optional-info"), allow(dead_code))]
+pub(crate) struct Row {
+    pub name: String,
+    pub number: String,
+    pub protocol: String,
+    pub description: String,
+    pub assignee: Option<String>,
+    pub contact: Option<String>,
+    pub registration_date: Option<String>,
+    pub modification_date: Option<String>,
+    pub reference: Option<String>,
+    pub service_code: Option<String>,
+    pub unauthorized_use: Option<String>,
+    pub assignment_notes: Option<String>,
+}
+
+/// The ports and protocol assigned by a row
+pub(crate) struct Assignment {
+    pub port: u16,
+    pub port_end: u16,
+    /// Position of the protocol in [`PROTOCOLS`], or `None` if the row has no
+    /// protocol (unassigned rows without one cover every protocol)
+    pub protocol: Option<usize>,
+}
+
+impl Row {
+    /// Whether the row lists unassigned ports rather than a service
+    pub fn is_unassigned(&self) -> bool {
+        self.name.is_empty() && self.description.eq_ignore_ascii_case("unassigned")
+    }
+
+    /// Read the port (or "start-end" range) and the protocol of the row
+    ///
+    /// Some service names are registered without a port; those rows return
+    /// `Ok(None)`. Malformed ports and unknown protocols return a description of the
+    /// problem.
+    pub fn assignment(&self) -> Result<Option<Assignment>, String> {
+        let (port, port_end) = match self.number.split_once('-') {
+            Some((start, end)) => match (start.trim().parse(), end.trim().parse()) {
+                (Ok(start), Ok(end)) if start <= end => (start, end),
+                _ => return Err(format!("invalid port range `{}`", self.number)),
+            },
+            None if self.number.is_empty() => return Ok(None),
+            None => match self.number.parse() {
+                Ok(port) => (port, port),
+                Err(_) => return Err(format!("invalid port `{}`", self.number)),
+            },
+        };
+
+        let protocol = match self.protocol.as_str() {
+            "" => None,
+            name => match PROTOCOLS.iter().position(|p| p.eq_ignore_ascii_case(name)) {
+                Some(key) => Some(key),
+                None => return Err(format!("unknown transport protocol `{}`", name)),
+            },
+        };
+
+        Ok(Some(Assignment {
+            port,
+            port_end,
+            protocol,
+        }))
+    }
+}
+
+/// A person or organization listed in the XML registry
+#[cfg_attr(not(feature = "optional-info"), allow(dead_code))]
+pub(crate) struct PersonRow {
+    pub id: String,
+    pub name: String,
+    pub org: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// Everything read from the XML registry
+#[derive(Default)]
+#[cfg_attr(not(feature = "optional-info"), allow(dead_code))]
+pub(crate) struct XmlRegistry {
+    /// Rows, with the line each one starts on
+    pub rows: Vec<(usize, Row)>,
+    /// Date the registry was last updated
+    pub updated: Option<String>,
+    /// People referenced by the records, sorted by id
+    pub people: Vec<PersonRow>,
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Read the rows of `service-names-port-numbers.csv`, with the line each one is on
+pub(crate) fn csv_rows<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = Result<(usize, Row), csv::Error>> {
+    csv::Reader::from_reader(reader)
+        .into_records()
+        .map(|result| {
+            let record = result?;
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            let field = |i| record.get(i).unwrap_or("").trim();
+
+            let row = Row {
+                name: field(0).to_string(),
+                number: field(1).to_string(),
+                protocol: field(2).to_string(),
+                description: field(3).to_string(),
+                assignee: non_empty(field(4)),
+                contact: non_empty(field(5)),
+                registration_date: non_empty(field(6)),
+                modification_date: non_empty(field(7)),
+                reference: non_empty(field(8)),
+                service_code: non_empty(field(9)),
+                unauthorized_use: non_empty(field(10)),
+                assignment_notes: non_empty(field(11)),
+            };
+            Ok((line, row))
+        })
+}
+
+/// Read `service-names-port-numbers.xml`
+///
+/// The registration and modification dates are the `date` and `updated` attributes
+/// of each `<record>`. Cross references are rendered the way the CSV renders them
+/// (`[RFC6335]`, `[Jon_Postel]`), one bracket per reference, so both formats give the
+/// same rows and each reference stays separately addressable.
+pub(crate) fn parse_xml(contents: &str) -> Result<XmlRegistry, roxmltree::Error> {
+    let document = roxmltree::Document::parse(contents)?;
+    let root = document.root_element();
+    let mut registry = XmlRegistry {
+        updated: root
+            .children()
+            .find(|node| node.has_tag_name("updated"))
+            .and_then(|node| non_empty(&element_text(node))),
+        ..Default::default()
+    };
+
+    for record in root.children().filter(|node| node.has_tag_name("record")) {
+        let line = document.text_pos_at(record.range().start).row as usize;
+        let mut row = Row {
+            registration_date: record.attribute("date").and_then(non_empty),
+            modification_date: record.attribute("updated").and_then(non_empty),
+            ..Default::default()
+        };
+        let mut references = Vec::new();
+
+        for child in record.children().filter(|node| node.is_element()) {
+            let text = element_text(child);
+            match child.tag_name().name() {
+                "name" => row.name = text,
+                "number" => row.number = text,
+                "protocol" => row.protocol = text,
+                "description" => row.description = text,
+                "assignee" => row.assignee = non_empty(&text),
+                "contact" => row.contact = non_empty(&text),
+                "service-code" => row.service_code = non_empty(&text),
+                "unauthorized-use" => row.unauthorized_use = non_empty(&text),
+                "assignment-notes" => row.assignment_notes = non_empty(&text),
+                "xref" => references.push(format_xref(child)),
+                _ => {}
+            }
+        }
+
+        row.reference = non_empty(&references.concat());
+        registry.rows.push((line, row));
+    }
+
+    for person in root
+        .children()
+        .filter(|node| node.has_tag_name("people"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("person"))
+    {
+        let field = |tag| {
+            person
+                .children()
+                .find(|node| node.has_tag_name(tag))
+                .and_then(|node| non_empty(&element_text(node)))
+        };
+        let Some(id) = person.attribute("id") else {
+            continue;
+        };
+
+        registry.people.push(PersonRow {
+            id: id.to_string(),
+            name: field("name").unwrap_or_else(|| id.to_string()),
+            org: field("org"),
+            uri: field("uri"),
+        });
+    }
+
+    registry.people.sort_by(|a, b| a.id.cmp(&b.id));
+    registry.people.dedup_by(|a, b| a.id == b.id);
+
+    Ok(registry)
+}
+
+/// The text of an element, with any cross references rendered inline
+fn element_text(node: roxmltree::Node) -> String {
+    let mut text = String::new();
+    for child in node.children() {
+        if child.has_tag_name("xref") {
+            text.push_str(&format_xref(child));
+        } else if child.is_text() {
+            text.push_str(child.text().unwrap_or(""));
+        } else {
+            text.push_str(&element_text(child));
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Render an `<xref>` element the way it appears in the CSV
+fn format_xref(node: roxmltree::Node) -> String {
+    let data = node.attribute("data").unwrap_or("");
+    match node.attribute("type") {
+        Some("rfc") => format!("[{}]", data.to_ascii_uppercase()),
+        Some("text") => element_text(node),
+        _ => format!("[{}]", data),
+    }
+}
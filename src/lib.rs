@@ -7,9 +7,12 @@
 //! # Features
 //!
 //! - **default**: Parse `/etc/services` at runtime (no build-time dependencies, ~125 KB)
-//! - **embed**: Fetch and embed the complete IANA registry at compile time (~6 MB, requires internet during build)
+//! - **embed**: Embed the complete IANA registry at compile time (~6 MB)
+//! - **fetch**: Download the latest IANA registry during the build instead of using the vendored snapshot (requires internet during build)
 //! - **optional-info**: Include description and extended metadata fields with embed mode (~15 MB total)
+//! - **iana-registry**: Load a local copy of the IANA registry (CSV or XML) into a `ServiceRegistry` at runtime, including the `optional-info` fields
 //! - **lookup-by-name**: Enable the `lookup_by_name` function and associated data (reduces size when only port lookups are needed)
+//! - **serde**: Implement `Serialize` and `Deserialize` for service records
 //!
 //! # Examples
 //!
@@ -48,17 +51,118 @@
 //! }
 //! ```
 
+mod class;
+mod date;
+mod date_parts;
+mod error;
+#[cfg(feature = "iana-registry")]
+mod iana;
+#[cfg(feature = "iana-registry")]
+mod iana_rows;
+mod name;
+mod owned;
+#[cfg(feature = "optional-info")]
+mod person;
+#[cfg(feature = "optional-info")]
+mod reference;
+#[cfg(feature = "optional-info")]
+mod reference_parts;
+mod registry;
+#[cfg(feature = "lookup-by-name")]
+mod search;
+mod spec;
+mod srv;
+#[cfg(feature = "optional-info")]
+mod text;
+#[cfg(feature = "optional-info")]
+mod text_tokens;
+
+use std::ops::{Bound, RangeBounds};
+
+pub use class::{PortClass, PortStatus, classify, port_status};
+#[cfg(feature = "optional-info")]
+pub use date::services_modified_since;
+pub use date::{Date, ParseDateError};
+pub use error::{Error, ParseWarning};
+pub use name::{MAX_SERVICE_NAME_LEN, NameError, validate_service_name};
+pub use owned::OwnedServiceRecord;
+#[cfg(feature = "optional-info")]
+pub use person::{Person, lookup_person};
+#[cfg(feature = "optional-info")]
+pub use reference::{Reference, lookup_by_rfc};
+pub use registry::{SYSTEM_SERVICES_PATH, ServiceRegistry};
+pub use spec::{ParseProtocolError, ParseSpecError, ServiceSpec};
+#[cfg(feature = "lookup-by-name")]
+pub use srv::resolve_srv_label;
+pub use srv::{SrvLabel, SrvLabelError};
+#[cfg(feature = "optional-info")]
+pub use text::search_text;
+
 /// Transport protocol for a service
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum TransportProtocol {
     /// Transmission Control Protocol
     Tcp,
     /// User Datagram Protocol
     Udp,
+    /// Stream Control Transmission Protocol
+    Sctp,
+    /// Datagram Congestion Control Protocol
+    Dccp,
+}
+
+impl TransportProtocol {
+    /// Key for this protocol in the indices generated by build.rs
+    #[cfg(feature = "embed")]
+    const fn index_key(self) -> u8 {
+        self as u8
+    }
 }
 
 /// A service record
+///
+/// With the `serde` feature, records can be serialized. Without `embed` they can also be
+/// deserialized; with `embed`, deserialize into an [`OwnedServiceRecord`] instead.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "serde")]
+/// {
+///     use iana_services::{OwnedServiceRecord, ServiceRegistry, all_services};
+///
+///     let registry = ServiceRegistry::parse("ssh 22/tcp sshd # Secure Shell\n");
+///     let ssh = registry.iter().next().unwrap().clone();
+///     #[cfg(feature = "optional-info")]
+///     let ssh = OwnedServiceRecord {
+///         registration_date: iana_services::Date::new(2011, 8, 18),
+///         ..ssh
+///     };
+///
+///     let json = serde_json::to_value(&ssh).unwrap();
+///     assert_eq!(json["name"], "ssh");
+///     assert_eq!(json["protocol"], "tcp");
+///     assert_eq!(json["aliases"], serde_json::json!(["sshd"]));
+///     #[cfg(feature = "optional-info")]
+///     assert_eq!(json["registration_date"], "2011-08-18");
+///     assert_eq!(serde_json::from_value::<OwnedServiceRecord>(json).unwrap(), ssh);
+///
+///     // Records from the global lookups deserialize into owned records
+///     for service in all_services().take(100) {
+///         let json = serde_json::to_string(&service).unwrap();
+///         let owned: OwnedServiceRecord = serde_json::from_str(&json).unwrap();
+///         assert_eq!(owned, OwnedServiceRecord::from(service.clone()));
+///     }
+/// }
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "embed")),
+    derive(serde::Deserialize)
+)]
 pub struct ServiceRecord {
     /// Service name (may be empty for reserved/unassigned ports)
     #[cfg(feature = "embed")]
@@ -66,12 +170,21 @@ pub struct ServiceRecord {
     #[cfg(not(feature = "embed"))]
     pub name: String,
 
-    /// Port number
+    /// Port number (the first port for assignments covering a range)
     pub port: u16,
 
-    /// Transport protocol (TCP or UDP)
+    /// Last port of the assignment (equal to `port` unless it covers a range)
+    pub port_end: u16,
+
+    /// Transport protocol (TCP, UDP, SCTP or DCCP)
     pub protocol: TransportProtocol,
 
+    /// Alternative names for the service (only found in `/etc/services`-style files)
+    #[cfg(feature = "embed")]
+    pub aliases: &'static [&'static str],
+    #[cfg(not(feature = "embed"))]
+    pub aliases: Vec<String>,
+
     /// Description of the service
     #[cfg(all(feature = "embed", feature = "optional-info"))]
     pub description: &'static str,
@@ -91,16 +204,12 @@ pub struct ServiceRecord {
     pub contact: Option<String>,
 
     /// Date the service was registered
-    #[cfg(all(feature = "embed", feature = "optional-info"))]
-    pub registration_date: Option<&'static str>,
-    #[cfg(all(not(feature = "embed"), feature = "optional-info"))]
-    pub registration_date: Option<String>,
+    #[cfg(feature = "optional-info")]
+    pub registration_date: Option<Date>,
 
     /// Date the service record was last modified
-    #[cfg(all(feature = "embed", feature = "optional-info"))]
-    pub modification_date: Option<&'static str>,
-    #[cfg(all(not(feature = "embed"), feature = "optional-info"))]
-    pub modification_date: Option<String>,
+    #[cfg(feature = "optional-info")]
+    pub modification_date: Option<Date>,
 
     /// Reference documentation (usually RFC numbers)
     #[cfg(all(feature = "embed", feature = "optional-info"))]
@@ -127,174 +236,313 @@ pub struct ServiceRecord {
     pub assignment_notes: Option<String>,
 }
 
+impl ServiceRecord {
+    /// All ports covered by this assignment
+    pub fn ports(&self) -> std::ops::RangeInclusive<u16> {
+        self.port..=self.port_end
+    }
+
+    /// Whether this assignment covers a range of ports rather than a single one
+    pub fn is_range(&self) -> bool {
+        self.port != self.port_end
+    }
+
+    /// Whether the given port falls within this assignment
+    pub fn contains_port(&self, port: u16) -> bool {
+        self.ports().contains(&port)
+    }
+
+    /// Whether any port of this assignment falls within `start..=end`
+    pub(crate) fn overlaps(&self, start: u16, end: u16) -> bool {
+        self.port <= end && self.port_end >= start
+    }
+}
+
+/// Convert port range bounds to inclusive `(start, end)`, or `None` if the range is empty
+pub(crate) fn port_bounds<R: RangeBounds<u16>>(range: R) -> Option<(u16, u16)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => u16::MIN,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end.checked_sub(1)?,
+        Bound::Unbounded => u16::MAX,
+    };
+
+    (start <= end).then_some((start, end))
+}
+
 #[cfg(feature = "embed")]
 mod embedded {
     use super::*;
 
     include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
+    pub fn records() -> &'static [ServiceRecord] {
+        SERVICE_RECORDS
+    }
+
+    pub fn updated() -> Option<Date> {
+        REGISTRY_UPDATED
+    }
+
+    /// A person as generated by build.rs, with the strings borrowed from the binary
+    #[cfg(feature = "optional-info")]
+    struct EmbeddedPerson {
+        id: &'static str,
+        name: &'static str,
+        org: Option<&'static str>,
+        uri: Option<&'static str>,
+    }
+
+    #[cfg(feature = "optional-info")]
+    impl From<&EmbeddedPerson> for Person {
+        fn from(person: &EmbeddedPerson) -> Self {
+            Person {
+                id: person.id.to_string(),
+                name: person.name.to_string(),
+                org: person.org.map(str::to_string),
+                uri: person.uri.map(str::to_string),
+            }
+        }
+    }
+
+    #[cfg(feature = "optional-info")]
+    pub fn person(id: &str) -> Option<Person> {
+        PEOPLE
+            .binary_search_by(|person| person.id.cmp(id))
+            .ok()
+            .map(|idx| Person::from(&PEOPLE[idx]))
+    }
+
+    #[cfg(feature = "optional-info")]
+    pub fn people() -> impl Iterator<Item = Person> {
+        PEOPLE.iter().map(Person::from)
+    }
+
+    pub fn services_in_range(
+        bounds: Option<(u16, u16)>,
+    ) -> impl Iterator<Item = &'static ServiceRecord> {
+        // Single-port records are sorted by port
+        let singles = &SERVICE_RECORDS[..RANGE_RECORDS_START];
+        let (from, to) = match bounds {
+            Some((start, end)) => (
+                singles.partition_point(|record| record.port < start),
+                singles.partition_point(|record| record.port <= end),
+            ),
+            None => (0, 0),
+        };
+
+        let ranges = SERVICE_RECORDS[RANGE_RECORDS_START..]
+            .iter()
+            .filter(move |record| bounds.is_some_and(|(start, end)| record.overlaps(start, end)));
+
+        singles[from..to].iter().chain(ranges)
+    }
+
+    pub fn unassigned_ports(
+        bounds: Option<(u16, u16)>,
+        protocol: TransportProtocol,
+    ) -> impl Iterator<Item = u16> {
+        UNASSIGNED[protocol.index_key() as usize]
+            .iter()
+            .filter_map(move |&(first, last)| {
+                let (start, end) = bounds?;
+                let (first, last) = (first.max(start), last.min(end));
+                (first <= last).then_some(first..=last)
+            })
+            .flatten()
+    }
+
+    pub fn is_unassigned(port: u16, protocol: TransportProtocol) -> bool {
+        // The ranges are sorted and disjoint
+        let ranges = UNASSIGNED[protocol.index_key() as usize];
+        let idx = ranges.partition_point(|&(_, last)| last < port);
+        ranges.get(idx).is_some_and(|&(first, _)| first <= port)
+    }
+
+    pub fn lookup_by_port_iter(port: u16) -> impl Iterator<Item = &'static ServiceRecord> {
+        let exact = match BY_PORT.get(&port) {
+            Some(&(start, end)) => &SERVICE_RECORDS[start..end],
+            None => &[],
+        };
+
+        // Ranged assignments aren't indexed by port
+        let ranges = SERVICE_RECORDS[RANGE_RECORDS_START..]
+            .iter()
+            .filter(move |record| record.contains_port(port));
+
+        exact.iter().chain(ranges)
+    }
+
+    pub fn lookup_iter(
+        port: u16,
+        protocol: TransportProtocol,
+    ) -> impl Iterator<Item = &'static ServiceRecord> {
+        let exact = match BY_PORT_PROTOCOL.get(&(port, protocol.index_key())) {
+            Some(&(start, end)) => &SERVICE_RECORDS[start..end],
+            None => &[],
+        };
+
+        let ranges = SERVICE_RECORDS[RANGE_RECORDS_START..]
+            .iter()
+            .filter(move |record| record.protocol == protocol && record.contains_port(port));
+
+        exact.iter().chain(ranges)
+    }
+
+    /// Lowercase a name into `buf` to look it up in the name indices, which are keyed
+    /// by the lowercased name. Returns `None` if the name is longer than any indexed
+    /// name, so lookups never allocate.
+    #[cfg(feature = "lookup-by-name")]
+    fn name_key<'b>(name: &str, buf: &'b mut [u8; MAX_NAME_LEN]) -> Option<&'b str> {
+        let key = buf.get_mut(..name.len())?;
+        key.copy_from_slice(name.as_bytes());
+        key.make_ascii_lowercase();
+        // Lowercasing ASCII bytes keeps the string valid UTF-8
+        std::str::from_utf8(key).ok()
+    }
+
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_by_name_iter(name: &str) -> impl Iterator<Item = &'static ServiceRecord> + use<> {
+        let mut buf = [0; MAX_NAME_LEN];
+        let indices: &'static [usize] = name_key(name, &mut buf)
+            .and_then(|key| BY_NAME.get(key))
+            .copied()
+            .unwrap_or_default();
+        indices.iter().map(|&idx| &SERVICE_RECORDS[idx])
+    }
+
     pub fn lookup_by_port_impl(port: u16) -> Option<Vec<ServiceRecord>> {
-        BY_PORT.get(&port).map(|(start, end)| {
-            SERVICE_RECORDS[*start..*end].to_vec()
-        })
+        to_vec(lookup_by_port_iter(port))
+    }
+
+    pub fn lookup_impl(port: u16, protocol: TransportProtocol) -> Option<Vec<ServiceRecord>> {
+        to_vec(lookup_iter(port, protocol))
     }
 
     #[cfg(feature = "lookup-by-name")]
     pub fn lookup_by_name_impl(name: &str) -> Option<Vec<ServiceRecord>> {
-        BY_NAME.get(name).map(|indices| {
-            indices.iter().map(|&idx| SERVICE_RECORDS[idx].clone()).collect()
-        })
+        to_vec(lookup_by_name_iter(name))
     }
-}
-
-#[cfg(not(feature = "embed"))]
-mod runtime {
-    use super::*;
-    use std::fs::File;
-    use std::io::{BufRead, BufReader};
-
-    fn parse_services_file<F>(mut callback: F) -> std::io::Result<()>
-    where
-        F: FnMut(String, u16, TransportProtocol, String) -> bool,
-    {
-        let file = File::open("/etc/services")?;
-        let reader = BufReader::new(file);
-
-        for line in reader.lines() {
-            let line = line?;
-            let line = line.trim();
-
-            // Skip comments and empty lines
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
 
-            // Parse line format: service port/protocol [aliases] [#comment]
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() < 2 {
-                continue;
-            }
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_by_name_strict_impl(name: &str) -> Option<Vec<ServiceRecord>> {
+        to_vec(lookup_by_name_iter(name).filter(|record| record.name == name))
+    }
 
-            let name = parts[0].to_string();
+    #[cfg(feature = "lookup-by-name")]
+    pub fn search_names_impl(query: &str) -> Vec<&'static str> {
+        crate::search::search(query, SORTED_NAMES)
+    }
 
-            // Parse port/protocol
-            let port_proto: Vec<&str> = parts[1].split('/').collect();
-            if port_proto.len() != 2 {
-                continue;
-            }
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_name_iter(
+        name: &str,
+        protocol: TransportProtocol,
+    ) -> impl Iterator<Item = &'static ServiceRecord> + use<> {
+        let mut buf = [0; MAX_NAME_LEN];
+        let indices: &'static [usize] = name_key(name, &mut buf)
+            .and_then(|key| BY_NAME_PROTOCOL.get(key))
+            .map_or(&[], |by_protocol| {
+                by_protocol[protocol.index_key() as usize]
+            });
+        indices.iter().map(|&idx| &SERVICE_RECORDS[idx])
+    }
 
-            let port: u16 = match port_proto[0].parse() {
-                Ok(p) => p,
-                Err(_) => continue,
-            };
-
-            let protocol = match port_proto[1].to_lowercase().as_str() {
-                "tcp" => TransportProtocol::Tcp,
-                "udp" => TransportProtocol::Udp,
-                _ => continue,
-            };
-
-            // Extract description (everything after first # or remaining parts as aliases)
-            let description = if let Some(comment_pos) = line.find('#') {
-                line[comment_pos + 1..].trim().to_string()
-            } else if parts.len() > 2 {
-                parts[2..].join(" ")
-            } else {
-                String::new()
-            };
-
-            // Call callback, if it returns false, stop iteration
-            if !callback(name, port, protocol, description) {
-                break;
-            }
-        }
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_name_impl(name: &str, protocol: TransportProtocol) -> Option<Vec<ServiceRecord>> {
+        to_vec(lookup_name_iter(name, protocol))
+    }
 
-        Ok(())
+    #[cfg(feature = "optional-info")]
+    pub fn lookup_by_rfc_impl(number: u32) -> Option<Vec<ServiceRecord>> {
+        let indices: &'static [usize] = BY_RFC.get(&number).copied().unwrap_or_default();
+        to_vec(indices.iter().map(|&idx| &SERVICE_RECORDS[idx]))
     }
 
-    pub fn lookup_by_port_impl(target_port: u16) -> Option<Vec<ServiceRecord>> {
-        let mut results = Vec::new();
-
-        let _ = parse_services_file(|name, port, protocol, _description| {
-            if port == target_port {
-                results.push(ServiceRecord {
-                    name,
-                    port,
-                    protocol,
-                    #[cfg(feature = "optional-info")]
-                    description,
-                    #[cfg(feature = "optional-info")]
-                    assignee: None,
-                    #[cfg(feature = "optional-info")]
-                    contact: None,
-                    #[cfg(feature = "optional-info")]
-                    registration_date: None,
-                    #[cfg(feature = "optional-info")]
-                    modification_date: None,
-                    #[cfg(feature = "optional-info")]
-                    reference: None,
-                    #[cfg(feature = "optional-info")]
-                    service_code: None,
-                    #[cfg(feature = "optional-info")]
-                    unauthorized_use: None,
-                    #[cfg(feature = "optional-info")]
-                    assignment_notes: None,
-                });
-            }
-            true // Continue searching
+    #[cfg(feature = "optional-info")]
+    pub fn search_text_impl(query: &str) -> Vec<ServiceRecord> {
+        let key = |idx: usize| {
+            let record = &SERVICE_RECORDS[idx];
+            let protocol = record.protocol.as_str();
+            (record.port, record.port_end, protocol, record.name)
+        };
+        let indices = crate::text::rank(query, key, |term| {
+            // The index is sorted by word, so the words starting with the term are
+            // contiguous
+            let start = TEXT_INDEX.partition_point(|(word, _)| *word < term);
+            TEXT_INDEX[start..]
+                .iter()
+                .take_while(|(word, _)| word.starts_with(term))
+                .flat_map(|(word, postings)| {
+                    postings
+                        .iter()
+                        .map(move |&(idx, fields)| (idx as usize, fields, *word == term))
+                })
+                .collect()
         });
 
+        indices
+            .into_iter()
+            .map(|idx| SERVICE_RECORDS[idx].clone())
+            .collect()
+    }
+
+    fn to_vec(records: impl Iterator<Item = &'static ServiceRecord>) -> Option<Vec<ServiceRecord>> {
+        let results: Vec<_> = records.cloned().collect();
+
         if results.is_empty() {
             None
         } else {
             Some(results)
         }
     }
+}
 
-    #[cfg(feature = "lookup-by-name")]
-    pub fn lookup_by_name_impl(target_name: &str) -> Option<Vec<ServiceRecord>> {
-        let mut results = Vec::new();
-
-        let _ = parse_services_file(|name, port, protocol, _description| {
-            if name == target_name {
-                results.push(ServiceRecord {
-                    name,
-                    port,
-                    protocol,
-                    #[cfg(feature = "optional-info")]
-                    description,
-                    #[cfg(feature = "optional-info")]
-                    assignee: None,
-                    #[cfg(feature = "optional-info")]
-                    contact: None,
-                    #[cfg(feature = "optional-info")]
-                    registration_date: None,
-                    #[cfg(feature = "optional-info")]
-                    modification_date: None,
-                    #[cfg(feature = "optional-info")]
-                    reference: None,
-                    #[cfg(feature = "optional-info")]
-                    service_code: None,
-                    #[cfg(feature = "optional-info")]
-                    unauthorized_use: None,
-                    #[cfg(feature = "optional-info")]
-                    assignment_notes: None,
-                });
-            }
-            true // Continue searching
-        });
+#[cfg(not(feature = "embed"))]
+mod runtime {
+    use super::*;
+    use std::sync::{Arc, OnceLock, RwLock};
 
-        if results.is_empty() {
-            None
-        } else {
-            Some(results)
+    /// The system services file, or the error from loading it, loaded on first use
+    static REGISTRY: OnceLock<RwLock<Result<Arc<ServiceRegistry>, Error>>> = OnceLock::new();
+
+    /// The system registry, or an empty one if it failed to load
+    pub fn registry() -> Arc<ServiceRegistry> {
+        try_registry().unwrap_or_default()
+    }
+
+    pub fn try_registry() -> Result<Arc<ServiceRegistry>, Error> {
+        let registry = REGISTRY
+            .get_or_init(|| RwLock::new(ServiceRegistry::system().map(Arc::new)))
+            .read()
+            .unwrap();
+
+        match &*registry {
+            Ok(registry) => Ok(registry.clone()),
+            Err(e) => Err(e.duplicate()),
         }
     }
+
+    pub fn reload_impl() -> Result<(), Error> {
+        set_registry_impl(ServiceRegistry::system()?);
+        Ok(())
+    }
+
+    pub fn set_registry_impl(registry: ServiceRegistry) {
+        let loaded = Arc::new(registry);
+        let registry = REGISTRY.get_or_init(|| RwLock::new(Ok(loaded.clone())));
+        *registry.write().unwrap() = Ok(loaded);
+    }
 }
 
 /// Look up services by port number
 ///
-/// Returns all service records (both TCP and UDP) associated with the given port number.
+/// Returns all service records (for every transport protocol) associated with the given port number,
+/// including assignments covering a range of ports that contains it.
 ///
 /// # Examples
 ///
@@ -312,12 +560,15 @@ pub fn lookup_by_port(port: u16) -> Option<Vec<ServiceRecord>> {
     return embedded::lookup_by_port_impl(port);
 
     #[cfg(not(feature = "embed"))]
-    return runtime::lookup_by_port_impl(port);
+    return runtime::registry().lookup_by_port(port);
 }
 
 /// Look up services by service name
 ///
-/// Returns all service records (across all protocols and ports) with the given name.
+/// Returns all service records (across all protocols and ports) with the given name
+/// or alias. Service names are case-insensitive (RFC 6335 §5.1), so `"HTTP"` finds
+/// the `http` records; the records keep the registry's spelling. Use
+/// [`lookup_by_name_strict`] to match the spelling exactly.
 ///
 /// The returned vector contains service records. Note that service names
 /// may map to multiple ports and protocols.
@@ -339,5 +590,420 @@ pub fn lookup_by_name(name: &str) -> Option<Vec<ServiceRecord>> {
     return embedded::lookup_by_name_impl(name);
 
     #[cfg(not(feature = "embed"))]
-    return runtime::lookup_by_name_impl(name);
+    return runtime::registry().lookup_by_name(name);
+}
+
+/// Look up services by service name, matching its spelling exactly
+///
+/// Like [`lookup_by_name`], but only returns records whose name (or alias) is
+/// spelled exactly like `name`, including its case.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::lookup_by_name_strict;
+///
+/// assert_eq!(lookup_by_name_strict("SSH"), None);
+/// ```
+#[cfg(feature = "lookup-by-name")]
+pub fn lookup_by_name_strict(name: &str) -> Option<Vec<ServiceRecord>> {
+    #[cfg(feature = "embed")]
+    return embedded::lookup_by_name_strict_impl(name);
+
+    #[cfg(not(feature = "embed"))]
+    return runtime::registry().lookup_by_name_strict(name);
+}
+
+/// Search service names, e.g. for completing a partially typed name
+///
+/// Matching ignores case. Names starting with the query come first, then names
+/// containing it, then names within a small edit distance of it (to catch typos).
+/// Each name is returned once, spelled as in the registry. With the `embed` feature,
+/// this searches a sorted name table generated at build time.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::search_names;
+///
+/// for name in search_names("kerb").into_iter().take(5) {
+///     println!("{name}");
+/// }
+/// ```
+#[cfg(all(feature = "lookup-by-name", feature = "embed"))]
+pub fn search_names(query: &str) -> Vec<&'static str> {
+    embedded::search_names_impl(query)
+}
+
+/// Search service names, e.g. for completing a partially typed name
+///
+/// Matching ignores case. Names starting with the query come first, then names
+/// containing it, then names within a small edit distance of it (to catch typos).
+/// Each name is returned once, spelled as in the registry. With the `embed` feature,
+/// this searches a sorted name table generated at build time.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::search_names;
+///
+/// for name in search_names("kerb").into_iter().take(5) {
+///     println!("{name}");
+/// }
+/// ```
+#[cfg(all(feature = "lookup-by-name", not(feature = "embed")))]
+pub fn search_names(query: &str) -> Vec<String> {
+    let registry = runtime::registry();
+    registry
+        .search_names(query)
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Look up services by port number without allocating
+///
+/// Like [`lookup_by_port`], but borrows the records embedded at compile time
+/// instead of cloning them into a new vector.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::lookup_by_port_iter;
+///
+/// for service in lookup_by_port_iter(22) {
+///     println!("Port 22: {} over {:?}", service.name, service.protocol);
+/// }
+///
+/// // X11 is registered on the range 6000-6063
+/// for port in [6000, 6010, 6063] {
+///     assert!(lookup_by_port_iter(port).any(|service| service.name == "x11"
+///         && (service.port, service.port_end) == (6000, 6063)));
+/// }
+/// assert!(lookup_by_port_iter(6064).all(|service| service.name != "x11"));
+/// ```
+#[cfg(feature = "embed")]
+pub fn lookup_by_port_iter(port: u16) -> impl Iterator<Item = &'static ServiceRecord> {
+    embedded::lookup_by_port_iter(port)
+}
+
+/// Look up services by service name without allocating
+///
+/// Like [`lookup_by_name`], but borrows the records embedded at compile time
+/// instead of cloning them into a new vector.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::lookup_by_name_iter;
+///
+/// for service in lookup_by_name_iter("ssh") {
+///     println!("SSH: port {} over {:?}", service.port, service.protocol);
+/// }
+/// assert_eq!(lookup_by_name_iter("SSH").count(), lookup_by_name_iter("ssh").count());
+/// ```
+#[cfg(all(feature = "embed", feature = "lookup-by-name"))]
+pub fn lookup_by_name_iter(name: &str) -> impl Iterator<Item = &'static ServiceRecord> + use<> {
+    embedded::lookup_by_name_iter(name)
+}
+
+/// Look up services by port number, reporting why nothing was found
+///
+/// Like [`lookup_by_port`], but distinguishes a port without registered services
+/// ([`Error::NotFound`]) from a services file that couldn't be read or parsed.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{try_lookup_by_port, Error};
+///
+/// match try_lookup_by_port(22) {
+///     Ok(services) => println!("Port 22: {}", services[0].name),
+///     Err(Error::NotFound) => println!("Port 22 isn't registered"),
+///     Err(e) => eprintln!("Lookup failed: {e}"),
+/// }
+/// ```
+pub fn try_lookup_by_port(port: u16) -> Result<Vec<ServiceRecord>, Error> {
+    #[cfg(feature = "embed")]
+    return embedded::lookup_by_port_impl(port).ok_or(Error::NotFound);
+
+    #[cfg(not(feature = "embed"))]
+    return runtime::try_registry()?
+        .lookup_by_port(port)
+        .ok_or(Error::NotFound);
+}
+
+/// Look up services by service name, reporting why nothing was found
+///
+/// Like [`lookup_by_name`], but distinguishes an unknown service name
+/// ([`Error::NotFound`]) from a services file that couldn't be read or parsed.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{try_lookup_by_name, Error};
+///
+/// match try_lookup_by_name("ssh") {
+///     Ok(services) => println!("SSH runs on port {}", services[0].port),
+///     Err(Error::NotFound) => println!("SSH isn't registered"),
+///     Err(e) => eprintln!("Lookup failed: {e}"),
+/// }
+/// ```
+#[cfg(feature = "lookup-by-name")]
+pub fn try_lookup_by_name(name: &str) -> Result<Vec<ServiceRecord>, Error> {
+    #[cfg(feature = "embed")]
+    return embedded::lookup_by_name_impl(name).ok_or(Error::NotFound);
+
+    #[cfg(not(feature = "embed"))]
+    return runtime::try_registry()?
+        .lookup_by_name(name)
+        .ok_or(Error::NotFound);
+}
+
+/// Look up services by port number and transport protocol
+///
+/// Like [`lookup_by_port`], but only returns the records for the given protocol,
+/// similar to `getservbyport`.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup, TransportProtocol};
+///
+/// if let Some(services) = lookup(3868, TransportProtocol::Sctp) {
+///     for service in &services {
+///         println!("Port 3868/sctp: {}", service.name);
+///     }
+/// }
+/// ```
+pub fn lookup(port: u16, protocol: TransportProtocol) -> Option<Vec<ServiceRecord>> {
+    #[cfg(feature = "embed")]
+    return embedded::lookup_impl(port, protocol);
+
+    #[cfg(not(feature = "embed"))]
+    return runtime::registry().lookup(port, protocol);
+}
+
+/// Look up services by service name and transport protocol
+///
+/// Like [`lookup_by_name`], but only returns the records for the given protocol,
+/// similar to `getservbyname`.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup_name, TransportProtocol};
+///
+/// if let Some(services) = lookup_name("diameter", TransportProtocol::Sctp) {
+///     for service in &services {
+///         println!("Diameter over SCTP: port {}", service.port);
+///     }
+/// }
+/// ```
+#[cfg(feature = "lookup-by-name")]
+pub fn lookup_name(name: &str, protocol: TransportProtocol) -> Option<Vec<ServiceRecord>> {
+    #[cfg(feature = "embed")]
+    return embedded::lookup_name_impl(name, protocol);
+
+    #[cfg(not(feature = "embed"))]
+    return runtime::registry().lookup_name(name, protocol);
+}
+
+/// Iterate over every service record in the registry
+///
+/// With the `embed` feature, this borrows the embedded records (single-port assignments
+/// sorted by port, followed by port ranges). Otherwise it yields copies of the records
+/// from `/etc/services`, in file order.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::all_services;
+///
+/// let sctp = all_services()
+///     .filter(|service| service.protocol == iana_services::TransportProtocol::Sctp)
+///     .count();
+/// println!("{sctp} services registered over SCTP");
+/// ```
+#[cfg(feature = "embed")]
+pub fn all_services() -> impl Iterator<Item = &'static ServiceRecord> {
+    embedded::records().iter()
+}
+
+/// Iterate over every service record in the registry
+///
+/// With the `embed` feature, this borrows the embedded records (single-port assignments
+/// sorted by port, followed by port ranges). Otherwise it yields copies of the records
+/// from `/etc/services`, in file order.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::all_services;
+///
+/// let sctp = all_services()
+///     .filter(|service| service.protocol == iana_services::TransportProtocol::Sctp)
+///     .count();
+/// println!("{sctp} services registered over SCTP");
+/// ```
+#[cfg(not(feature = "embed"))]
+pub fn all_services() -> impl Iterator<Item = ServiceRecord> {
+    let registry = runtime::registry();
+    (0..registry.len()).map(move |idx| registry.records()[idx].clone())
+}
+
+/// Iterate over the services registered on ports within a range
+///
+/// Assignments covering a range of ports are included if any of their ports fall
+/// within the given range. With the `embed` feature, this borrows the embedded
+/// records and finds the single-port ones with a binary search.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::services_in_range;
+///
+/// for service in services_in_range(8000..=8100) {
+///     println!("{}/{:?}: {}", service.port, service.protocol, service.name);
+/// }
+/// ```
+#[cfg(feature = "embed")]
+pub fn services_in_range<R: RangeBounds<u16>>(
+    range: R,
+) -> impl Iterator<Item = &'static ServiceRecord> {
+    embedded::services_in_range(port_bounds(range))
+}
+
+/// Iterate over the services registered on ports within a range
+///
+/// Assignments covering a range of ports are included if any of their ports fall
+/// within the given range. With the `embed` feature, this borrows the embedded
+/// records and finds the single-port ones with a binary search.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::services_in_range;
+///
+/// for service in services_in_range(8000..=8100) {
+///     println!("{}/{:?}: {}", service.port, service.protocol, service.name);
+/// }
+/// ```
+#[cfg(not(feature = "embed"))]
+pub fn services_in_range<R: RangeBounds<u16>>(range: R) -> impl Iterator<Item = ServiceRecord> {
+    let registry = runtime::registry();
+    let services: Vec<_> = registry.services_in_range(range).cloned().collect();
+    services.into_iter()
+}
+
+/// Iterate over the ports in a range that IANA lists as unassigned for a protocol
+///
+/// Ports are yielded in ascending order. Ports that are reserved, or assigned to any
+/// service over the given protocol, are never included.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup, unassigned_ports, TransportProtocol};
+///
+/// let free: Vec<u16> = unassigned_ports(8000..=8100, TransportProtocol::Tcp).collect();
+/// println!("{} unassigned TCP ports between 8000 and 8100", free.len());
+///
+/// assert!(!free.is_empty());
+/// assert!(free.is_sorted() && free.iter().all(|port| (8000..=8100).contains(port)));
+/// // 8080 is registered as http-alt
+/// assert!(!free.contains(&8080));
+/// assert!(free.iter().all(|&port| lookup(port, TransportProtocol::Tcp).is_none()));
+/// ```
+#[cfg(feature = "embed")]
+pub fn unassigned_ports<R: RangeBounds<u16>>(
+    range: R,
+    protocol: TransportProtocol,
+) -> impl Iterator<Item = u16> {
+    embedded::unassigned_ports(port_bounds(range), protocol)
+}
+
+/// Check whether IANA lists a port as unassigned for a protocol
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{is_unassigned, unassigned_ports, TransportProtocol};
+///
+/// assert!(!is_unassigned(22, TransportProtocol::Tcp));
+/// assert!(!is_unassigned(8080, TransportProtocol::Tcp));
+///
+/// let free = unassigned_ports(8000..9000, TransportProtocol::Tcp).next().unwrap();
+/// assert!(is_unassigned(free, TransportProtocol::Tcp));
+/// ```
+#[cfg(feature = "embed")]
+pub fn is_unassigned(port: u16, protocol: TransportProtocol) -> bool {
+    embedded::is_unassigned(port, protocol)
+}
+
+/// Suggest a port in a range that IANA hasn't registered for a protocol
+///
+/// Returns the lowest unassigned port in the range, or `None` if every port in
+/// the range is assigned or reserved.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{suggest_unassigned_port, TransportProtocol};
+///
+/// if let Some(port) = suggest_unassigned_port(8000..9000, TransportProtocol::Tcp) {
+///     println!("Defaulting to port {port}");
+/// }
+///
+/// let port = suggest_unassigned_port(8000..9000, TransportProtocol::Tcp).unwrap();
+/// assert!((8000..9000).contains(&port));
+/// assert!(iana_services::is_unassigned(port, TransportProtocol::Tcp));
+/// assert!(iana_services::lookup(port, TransportProtocol::Tcp).is_none());
+///
+/// // Port 22 is taken, so a range of just that port has nothing to suggest
+/// assert_eq!(suggest_unassigned_port(22..=22, TransportProtocol::Tcp), None);
+/// ```
+#[cfg(feature = "embed")]
+pub fn suggest_unassigned_port<R: RangeBounds<u16>>(
+    range: R,
+    protocol: TransportProtocol,
+) -> Option<u16> {
+    unassigned_ports(range, protocol).next()
+}
+
+/// Reload the system services file
+///
+/// The services file is parsed once on first use and cached for all later lookups.
+/// Long-running processes can call this to pick up changes made since then. If the
+/// file can't be read, the error is returned and the previous contents stay in use.
+///
+/// # Examples
+///
+/// ```
+/// if let Err(e) = iana_services::reload() {
+///     eprintln!("Failed to reload /etc/services: {e}");
+/// }
+/// ```
+#[cfg(not(feature = "embed"))]
+pub fn reload() -> Result<(), Error> {
+    runtime::reload_impl()
+}
+
+/// Use another registry for all later lookups instead of the system services file
+///
+/// This lets a copy of the IANA registry loaded at runtime (`ServiceRegistry::from_iana_path`
+/// with the `iana-registry` feature) back the global lookup functions. A later call to
+/// [`reload`] switches back to the system services file.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup_by_port, set_registry, ServiceRegistry};
+///
+/// set_registry(ServiceRegistry::parse("my-app 7777/tcp\n"));
+/// assert_eq!(lookup_by_port(7777).unwrap()[0].name, "my-app");
+/// ```
+#[cfg(not(feature = "embed"))]
+pub fn set_registry(registry: ServiceRegistry) {
+    runtime::set_registry_impl(registry)
 }
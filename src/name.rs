@@ -0,0 +1,121 @@
+use std::fmt;
+
+/// Longest service name allowed by RFC 6335 §5.1
+pub const MAX_SERVICE_NAME_LEN: usize = 15;
+
+/// Check that a service name follows the syntax of RFC 6335 §5.1
+///
+/// Valid names are 1 to 15 characters long, contain only ASCII letters, digits and
+/// hyphens, contain at least one letter, and don't start or end with a hyphen or
+/// contain two hyphens in a row. The same rules apply to Kubernetes port names and to
+/// the service labels of DNS-SD.
+///
+/// Some names registered before RFC 6335 don't follow these rules; see
+/// [`ServiceRegistry::invalid_names`](crate::ServiceRegistry::invalid_names).
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{validate_service_name, NameError};
+///
+/// assert_eq!(validate_service_name("http-alt"), Ok(()));
+/// assert_eq!(validate_service_name("8080"), Err(NameError::NoLetter));
+/// assert_eq!(
+///     validate_service_name("my--app"),
+///     Err(NameError::ConsecutiveHyphens { position: 2 })
+/// );
+/// ```
+pub fn validate_service_name(name: &str) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+
+    let len = name.chars().count();
+    if len > MAX_SERVICE_NAME_LEN {
+        return Err(NameError::TooLong { len });
+    }
+
+    if name.starts_with('-') {
+        return Err(NameError::LeadingHyphen);
+    }
+
+    let mut previous = None;
+    for (position, c) in name.chars().enumerate() {
+        if !c.is_ascii_alphanumeric() && c != '-' {
+            return Err(NameError::InvalidCharacter { c, position });
+        }
+        if c == '-' && previous == Some('-') {
+            return Err(NameError::ConsecutiveHyphens {
+                position: position - 1,
+            });
+        }
+        previous = Some(c);
+    }
+
+    if name.ends_with('-') {
+        return Err(NameError::TrailingHyphen);
+    }
+    if !name.bytes().any(|b| b.is_ascii_alphabetic()) {
+        return Err(NameError::NoLetter);
+    }
+
+    Ok(())
+}
+
+/// Why a service name breaks the rules of RFC 6335 §5.1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameError {
+    /// The name is empty
+    Empty,
+    /// The name is longer than 15 characters
+    TooLong {
+        /// Length of the name in characters
+        len: usize,
+    },
+    /// The name contains a character other than a letter, digit or hyphen
+    InvalidCharacter {
+        /// The offending character
+        c: char,
+        /// Position of the character (in characters, starting at 0)
+        position: usize,
+    },
+    /// The name has no letters
+    NoLetter,
+    /// The name starts with a hyphen
+    LeadingHyphen,
+    /// The name ends with a hyphen
+    TrailingHyphen,
+    /// The name contains two hyphens in a row
+    ConsecutiveHyphens {
+        /// Position of the first hyphen (in characters, starting at 0)
+        position: usize,
+    },
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "service name is empty"),
+            NameError::TooLong { len } => write!(
+                f,
+                "service name is {} characters long, at most {} are allowed",
+                len, MAX_SERVICE_NAME_LEN
+            ),
+            NameError::InvalidCharacter { c, position } => write!(
+                f,
+                "service name contains `{}` at position {}, only letters, digits and hyphens are allowed",
+                c, position
+            ),
+            NameError::NoLetter => write!(f, "service name must contain at least one letter"),
+            NameError::LeadingHyphen => write!(f, "service name must not start with a hyphen"),
+            NameError::TrailingHyphen => write!(f, "service name must not end with a hyphen"),
+            NameError::ConsecutiveHyphens { position } => write!(
+                f,
+                "service name contains consecutive hyphens at position {}",
+                position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
@@ -0,0 +1,146 @@
+#[cfg(all(feature = "embed", feature = "optional-info"))]
+use crate::Date;
+use crate::ServiceRecord;
+#[cfg(feature = "embed")]
+use crate::TransportProtocol;
+
+/// An owned service record
+///
+/// Without the `embed` feature, [`ServiceRecord`] already owns its data, so this is
+/// just an alias for it.
+#[cfg(not(feature = "embed"))]
+pub type OwnedServiceRecord = ServiceRecord;
+
+/// An owned service record
+///
+/// With the `embed` feature, [`ServiceRecord`] borrows its strings from the embedded
+/// registry. This type holds owned copies of the same fields, so it can be built at
+/// runtime, e.g. when deserializing records that were serialized from a [`ServiceRecord`].
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup_by_port_iter, OwnedServiceRecord};
+///
+/// let owned: Vec<OwnedServiceRecord> = lookup_by_port_iter(22).map(Into::into).collect();
+/// ```
+#[cfg(feature = "embed")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedServiceRecord {
+    /// Service name (may be empty for reserved ports)
+    pub name: String,
+
+    /// Port number (the first port for assignments covering a range)
+    pub port: u16,
+
+    /// Last port of the assignment (equal to `port` unless it covers a range)
+    pub port_end: u16,
+
+    /// Transport protocol (TCP, UDP, SCTP or DCCP)
+    pub protocol: TransportProtocol,
+
+    /// Alternative names for the service
+    pub aliases: Vec<String>,
+
+    /// Description of the service
+    #[cfg(feature = "optional-info")]
+    pub description: String,
+
+    /// Organization or person to whom the port is assigned
+    #[cfg(feature = "optional-info")]
+    pub assignee: Option<String>,
+
+    /// Contact information for the assignee
+    #[cfg(feature = "optional-info")]
+    pub contact: Option<String>,
+
+    /// Date the service was registered
+    #[cfg(feature = "optional-info")]
+    pub registration_date: Option<Date>,
+
+    /// Date the service record was last modified
+    #[cfg(feature = "optional-info")]
+    pub modification_date: Option<Date>,
+
+    /// Reference documentation (usually RFC numbers)
+    #[cfg(feature = "optional-info")]
+    pub reference: Option<String>,
+
+    /// Service code
+    #[cfg(feature = "optional-info")]
+    pub service_code: Option<String>,
+
+    /// Whether unauthorized use has been reported
+    #[cfg(feature = "optional-info")]
+    pub unauthorized_use: Option<String>,
+
+    /// Additional notes about the assignment
+    #[cfg(feature = "optional-info")]
+    pub assignment_notes: Option<String>,
+}
+
+#[cfg(feature = "embed")]
+impl OwnedServiceRecord {
+    /// All ports covered by this assignment
+    pub fn ports(&self) -> std::ops::RangeInclusive<u16> {
+        self.port..=self.port_end
+    }
+
+    /// Whether this assignment covers a range of ports rather than a single one
+    pub fn is_range(&self) -> bool {
+        self.port != self.port_end
+    }
+
+    /// Whether the given port falls within this assignment
+    pub fn contains_port(&self, port: u16) -> bool {
+        self.ports().contains(&port)
+    }
+
+    /// Whether any port of this assignment falls within `start..=end`
+    pub(crate) fn overlaps(&self, start: u16, end: u16) -> bool {
+        self.port <= end && self.port_end >= start
+    }
+}
+
+#[cfg(feature = "embed")]
+impl From<&ServiceRecord> for OwnedServiceRecord {
+    fn from(record: &ServiceRecord) -> Self {
+        Self {
+            name: record.name.to_string(),
+            port: record.port,
+            port_end: record.port_end,
+            protocol: record.protocol,
+            aliases: record
+                .aliases
+                .iter()
+                .map(|alias| alias.to_string())
+                .collect(),
+            #[cfg(feature = "optional-info")]
+            description: record.description.to_string(),
+            #[cfg(feature = "optional-info")]
+            assignee: record.assignee.map(str::to_string),
+            #[cfg(feature = "optional-info")]
+            contact: record.contact.map(str::to_string),
+            #[cfg(feature = "optional-info")]
+            registration_date: record.registration_date,
+            #[cfg(feature = "optional-info")]
+            modification_date: record.modification_date,
+            #[cfg(feature = "optional-info")]
+            reference: record.reference.map(str::to_string),
+            #[cfg(feature = "optional-info")]
+            service_code: record.service_code.map(str::to_string),
+            #[cfg(feature = "optional-info")]
+            unauthorized_use: record.unauthorized_use.map(str::to_string),
+            #[cfg(feature = "optional-info")]
+            assignment_notes: record.assignment_notes.map(str::to_string),
+        }
+    }
+}
+
+#[cfg(feature = "embed")]
+impl From<ServiceRecord> for OwnedServiceRecord {
+    fn from(record: ServiceRecord) -> Self {
+        Self::from(&record)
+    }
+}
@@ -0,0 +1,52 @@
+/// A person or organization referenced by the registry, e.g. as an assignee or contact
+///
+/// Records refer to people by id in brackets (`[Jon_Postel]`). Only the XML format of
+/// the IANA registry lists their details; the CSV and `/etc/services` don't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Person {
+    /// Id used to refer to the person from records
+    pub id: String,
+
+    /// Full name of the person or organization
+    pub name: String,
+
+    /// Organization the person belongs to
+    pub org: Option<String>,
+
+    /// Contact URI (usually `mailto:`)
+    pub uri: Option<String>,
+}
+
+/// Look up a person referenced by the registry
+///
+/// Accepts the id with or without the surrounding brackets used in
+/// [`ServiceRecord::assignee`](crate::ServiceRecord::assignee) and
+/// [`ServiceRecord::contact`](crate::ServiceRecord::contact).
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{lookup_by_port, lookup_person};
+///
+/// for service in lookup_by_port(22).into_iter().flatten() {
+///     if let Some(person) = service.contact.as_deref().and_then(lookup_person) {
+///         println!("{}: {} ({:?})", service.name, person.name, person.uri);
+///     }
+/// }
+/// ```
+pub fn lookup_person(id: &str) -> Option<Person> {
+    #[cfg(feature = "embed")]
+    return crate::embedded::person(person_id(id));
+
+    #[cfg(not(feature = "embed"))]
+    return crate::runtime::registry().person(id);
+}
+
+/// Strip the brackets records put around person ids
+pub(crate) fn person_id(id: &str) -> &str {
+    let id = id.trim();
+    id.strip_prefix('[')
+        .and_then(|id| id.strip_suffix(']'))
+        .unwrap_or(id)
+}
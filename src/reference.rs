@@ -0,0 +1,130 @@
+use crate::ServiceRecord;
+use crate::reference_parts::{self, Part};
+use std::fmt;
+
+/// A reference from a service record to the document or person behind it
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::Reference;
+///
+/// let references: Vec<_> = Reference::parse("[RFC9110][Roy_Fielding]").collect();
+/// assert_eq!(references, [Reference::Rfc(9110), Reference::Person("Roy_Fielding")]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reference<'a> {
+    /// An RFC, by number
+    Rfc(u32),
+    /// A person or organization, by id (see [`lookup_person`](crate::lookup_person))
+    Person(&'a str),
+    /// Anything else, e.g. an Internet-Draft, a URL or free text
+    Other(&'a str),
+}
+
+impl<'a> Reference<'a> {
+    /// Parse a reference field like `[RFC7230][RFC9110]`
+    ///
+    /// Every bracketed part becomes one reference, and so does any text between them.
+    /// Bracketed ids made only of letters, digits, `_`, `-` and `.` are taken to be
+    /// people, except for Internet-Drafts (`draft-...`).
+    pub fn parse(reference: &'a str) -> impl Iterator<Item = Reference<'a>> + 'a {
+        reference_parts::parts(reference).map(|part| match part {
+            Part::Bracketed(id) => Self::from_bracketed(id),
+            Part::Text(text) => Reference::Other(text),
+        })
+    }
+
+    fn from_bracketed(id: &'a str) -> Self {
+        if let Some(number) = reference_parts::rfc_number(id) {
+            return Reference::Rfc(number);
+        }
+
+        let is_person = !id.starts_with("draft-")
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+        if is_person {
+            Reference::Person(id)
+        } else {
+            Reference::Other(id)
+        }
+    }
+}
+
+impl fmt::Display for Reference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reference::Rfc(number) => write!(f, "[RFC{}]", number),
+            Reference::Person(id) => write!(f, "[{}]", id),
+            Reference::Other(text) => f.write_str(text),
+        }
+    }
+}
+
+impl ServiceRecord {
+    /// The references of this record, parsed from [`reference`](Self::reference)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::{lookup_by_port, Reference};
+    ///
+    /// for service in lookup_by_port(443).into_iter().flatten() {
+    ///     for reference in service.references() {
+    ///         if let Reference::Rfc(number) = reference {
+    ///             println!("{} is defined by RFC {}", service.name, number);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn references(&self) -> impl Iterator<Item = Reference<'_>> {
+        #[cfg(feature = "embed")]
+        let reference = self.reference.unwrap_or("");
+        #[cfg(not(feature = "embed"))]
+        let reference = self.reference.as_deref().unwrap_or("");
+
+        Reference::parse(reference)
+    }
+
+    /// Whether this record references the given RFC
+    pub fn references_rfc(&self, number: u32) -> bool {
+        self.references()
+            .any(|reference| reference == Reference::Rfc(number))
+    }
+}
+
+#[cfg(feature = "embed")]
+impl crate::OwnedServiceRecord {
+    /// The references of this record, parsed from [`reference`](Self::reference)
+    pub fn references(&self) -> impl Iterator<Item = Reference<'_>> {
+        Reference::parse(self.reference.as_deref().unwrap_or(""))
+    }
+
+    /// Whether this record references the given RFC
+    pub fn references_rfc(&self, number: u32) -> bool {
+        self.references()
+            .any(|reference| reference == Reference::Rfc(number))
+    }
+}
+
+/// Look up the services whose registration references an RFC
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::lookup_by_rfc;
+///
+/// // Compliance report: which ports come from the HTTP semantics RFC?
+/// for service in lookup_by_rfc(9110).into_iter().flatten() {
+///     println!("{}/{} ({})", service.port, service.protocol, service.name);
+/// }
+/// ```
+pub fn lookup_by_rfc(number: u32) -> Option<Vec<ServiceRecord>> {
+    #[cfg(feature = "embed")]
+    return crate::embedded::lookup_by_rfc_impl(number);
+
+    #[cfg(not(feature = "embed"))]
+    return crate::runtime::registry().lookup_by_rfc(number);
+}
@@ -0,0 +1,61 @@
+//! Splitting the reference field of the IANA registry
+//!
+//! This file is shared by build.rs (through `#[path]`) and `Reference::parse`, so the
+//! RFC index generated for `embed` reads references the same way the runtime does.
+//! It can't use anything else from the crate.
+
+/// A part of a reference field like `[RFC7230][Roy_Fielding]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Part<'a> {
+    /// An id between brackets, trimmed and never empty
+    Bracketed(&'a str),
+    /// Text outside of brackets, trimmed and never empty
+    Text(&'a str),
+}
+
+/// Split a reference field into its bracketed ids and the text between them
+pub(crate) fn parts(reference: &str) -> impl Iterator<Item = Part<'_>> {
+    let mut rest = reference;
+
+    std::iter::from_fn(move || {
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                return None;
+            }
+
+            let part = match rest.strip_prefix('[') {
+                Some(inner) => {
+                    let end = inner.find(']').unwrap_or(inner.len());
+                    rest = inner.get(end + 1..).unwrap_or("");
+                    Part::Bracketed(inner[..end].trim())
+                }
+                None => {
+                    let end = rest.find('[').unwrap_or(rest.len());
+                    let text = rest[..end].trim();
+                    rest = &rest[end..];
+                    Part::Text(text)
+                }
+            };
+
+            match part {
+                Part::Bracketed(text) | Part::Text(text) if text.is_empty() => {}
+                part => return Some(part),
+            }
+        }
+    })
+}
+
+/// The number of an RFC id like `RFC6335` (or `rfc 6335`)
+pub(crate) fn rfc_number(id: &str) -> Option<u32> {
+    let prefix = id.get(..3)?;
+    if !prefix.eq_ignore_ascii_case("rfc") {
+        return None;
+    }
+
+    let number = id[3..].trim_start();
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
@@ -0,0 +1,761 @@
+#[cfg(feature = "optional-info")]
+use crate::Person;
+use crate::{Date, Error, NameError, OwnedServiceRecord, ParseWarning, TransportProtocol};
+#[cfg(feature = "optional-info")]
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+#[cfg(feature = "optional-info")]
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::path::Path;
+#[cfg(feature = "lookup-by-name")]
+use std::sync::OnceLock;
+
+/// Path of the system services file
+pub const SYSTEM_SERVICES_PATH: &str = "/etc/services";
+
+/// A collection of service records that can be queried like the global lookup functions
+///
+/// A registry can be loaded from any file in `/etc/services` format, so several
+/// registries (e.g. from fixture files or a container's root filesystem) can be used
+/// side by side. With the `embed` feature, [`embedded`](Self::embedded) provides the
+/// IANA registry embedded at compile time as one more registry.
+///
+/// The registry owns its records, so it holds [`OwnedServiceRecord`]s, which are the
+/// same as [`ServiceRecord`](crate::ServiceRecord)s unless `embed` is enabled.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::ServiceRegistry;
+///
+/// let registry = ServiceRegistry::parse("ssh 22/tcp\nhttp 80/tcp www www-http\n");
+/// let services = registry.lookup_by_port(80).unwrap();
+/// assert_eq!(services[0].name, "http");
+/// assert_eq!(services[0].aliases, ["www", "www-http"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ServiceRegistry {
+    records: Vec<OwnedServiceRecord>,
+    by_port: HashMap<u16, Vec<usize>>,
+    ranges: Vec<usize>,
+    #[cfg(feature = "lookup-by-name")]
+    by_name: HashMap<String, Vec<usize>>,
+    /// `(lowercased name, spelling)` pairs sorted for [`search_names`](Self::search_names),
+    /// built on first use and cleared whenever a record is added
+    #[cfg(feature = "lookup-by-name")]
+    sorted_names: OnceLock<Vec<(String, String)>>,
+    #[cfg(feature = "optional-info")]
+    by_rfc: HashMap<u32, Vec<usize>>,
+    /// Words of the searchable fields, with the records and fields they appear in
+    #[cfg(feature = "optional-info")]
+    text_index: BTreeMap<String, Vec<(usize, u8)>>,
+    updated: Option<Date>,
+    #[cfg(feature = "optional-info")]
+    people: Vec<Person>,
+    warnings: Vec<ParseWarning>,
+}
+
+impl ServiceRegistry {
+    /// Copy the IANA registry embedded at compile time into a registry
+    ///
+    /// This lets the embedded registry be queried side by side with registries loaded
+    /// from files. The global lookup functions query the embedded tables directly,
+    /// without copying them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let embedded = ServiceRegistry::embedded();
+    /// let local = ServiceRegistry::parse("my-app 22/tcp\n");
+    /// for registry in [&embedded, &local] {
+    ///     if let Some(services) = registry.lookup_by_port(22) {
+    ///         println!("Port 22: {}", services[0].name);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "embed")]
+    pub fn embedded() -> Self {
+        let mut registry = Self::default();
+        for record in crate::embedded::records() {
+            registry.insert(record.into());
+        }
+        registry.updated = crate::embedded::updated();
+        #[cfg(feature = "optional-info")]
+        {
+            registry.people = crate::embedded::people().collect();
+        }
+        registry
+    }
+
+    /// Load the system services file (`/etc/services`)
+    pub fn system() -> Result<Self, Error> {
+        Self::from_path(SYSTEM_SERVICES_PATH)
+    }
+
+    /// Load a services file from the given path
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Load services from a reader in `/etc/services` format
+    ///
+    /// Malformed lines, including lines that aren't valid UTF-8, are skipped and
+    /// reported through [`warnings`](Self::warnings). Reading only stops with an error
+    /// if the reader fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let registry = ServiceRegistry::from_reader(&b"ok 1/tcp # caf\xe9\nssh 22/tcp\n"[..])?;
+    /// assert_eq!(registry.len(), 1);
+    /// assert_eq!(registry.lookup_by_port(22).unwrap()[0].name, "ssh");
+    /// assert_eq!(registry.warnings()[0].line, 1);
+    /// # Ok::<(), iana_services::Error>(())
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, Error> {
+        let mut registry = Self::default();
+        let mut warnings = Vec::new();
+
+        parse_services_file(
+            reader,
+            &mut warnings,
+            |name, port, protocol, aliases, description| {
+                registry.insert(new_record(name, port, protocol, aliases, description));
+                true
+            },
+        )?;
+
+        registry.warnings = warnings;
+        Ok(registry)
+    }
+
+    /// Load a copy of the IANA registry, in either its CSV or XML format
+    ///
+    /// Files ending in `.xml` are parsed as XML (e.g. the `services.xml` shipped by
+    /// iana-etc), anything else as CSV. Unlike `/etc/services`, these files carry the
+    /// assignee, contact, dates and references, so with `optional-info` the records
+    /// match the ones embedded by the `embed` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let registry = ServiceRegistry::from_iana_path("/usr/share/iana-etc/services.xml")?;
+    /// # Ok::<(), iana_services::Error>(())
+    /// ```
+    #[cfg(feature = "iana-registry")]
+    pub fn from_iana_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+        {
+            Self::from_iana_xml(file)
+        } else {
+            Self::from_iana_csv(BufReader::new(file))
+        }
+    }
+
+    /// Load the IANA registry from a reader in its CSV format
+    /// (`service-names-port-numbers.csv`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let csv = "\
+    /// Service Name,Port Number,Transport Protocol,Description,Assignee,Contact,Registration Date,Modification Date,Reference,Service Code,Unauthorized Use Reported,Assignment Notes
+    /// ssh,22,tcp,The Secure Shell (SSH) Protocol,,,,,[RFC4251],,,
+    /// x11,6000-6063,tcp,X Window System,[Stephen_Gildea],[Stephen_Gildea],,,,,,
+    /// ";
+    /// let registry = ServiceRegistry::from_iana_csv(csv.as_bytes()).unwrap();
+    /// assert_eq!(registry.lookup_by_port(22).unwrap()[0].name, "ssh");
+    /// assert_eq!(registry.lookup_by_port(6010).unwrap()[0].name, "x11");
+    /// ```
+    #[cfg(feature = "iana-registry")]
+    pub fn from_iana_csv<R: std::io::Read>(reader: R) -> Result<Self, Error> {
+        let mut warnings = Vec::new();
+        let parsed = crate::iana::parse_csv(reader, &mut warnings)?;
+        Ok(Self::from_parsed(parsed, warnings))
+    }
+
+    /// Load the IANA registry from a reader in its XML format
+    /// (`service-names-port-numbers.xml`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let xml = r#"<?xml version='1.0' encoding='UTF-8'?>
+    /// <registry xmlns="http://www.iana.org/assignments" id="service-names-port-numbers">
+    ///   <title>Service Name and Transport Protocol Port Number Registry</title>
+    ///   <updated>2025-06-12</updated>
+    ///   <record>
+    ///     <name>ssh</name>
+    ///     <protocol>tcp</protocol>
+    ///     <description>The Secure Shell (SSH) Protocol</description>
+    ///     <xref type="rfc" data="rfc4251"/>
+    ///     <number>22</number>
+    ///   </record>
+    ///   <record date="2011-08-18" updated="2017-05-16">
+    ///     <name>example-svc</name>
+    ///     <protocol>udp</protocol>
+    ///     <description>Example Service</description>
+    ///     <assignee><xref type="person" data="Jane_Doe"/></assignee>
+    ///     <contact><xref type="person" data="Jane_Doe"/></contact>
+    ///     <number>4567</number>
+    ///   </record>
+    ///   <people>
+    ///     <person id="Jane_Doe">
+    ///       <name>Jane Doe</name>
+    ///       <uri>mailto:jane@example.com</uri>
+    ///     </person>
+    ///   </people>
+    /// </registry>"#;
+    /// let registry = ServiceRegistry::from_iana_xml(xml.as_bytes()).unwrap();
+    /// assert_eq!(registry.lookup_by_port(22).unwrap()[0].name, "ssh");
+    /// assert_eq!(registry.updated(), "2025-06-12".parse().ok());
+    ///
+    /// #[cfg(feature = "optional-info")]
+    /// {
+    ///     let service = &registry.lookup_by_port(4567).unwrap()[0];
+    ///     assert_eq!(service.registration_date, "2011-08-18".parse().ok());
+    ///     assert_eq!(service.modification_date, "2017-05-16".parse().ok());
+    ///     assert_eq!(service.reference, None);
+    ///     assert_eq!(registry.person("[Jane_Doe]").unwrap().name, "Jane Doe");
+    /// }
+    /// ```
+    #[cfg(feature = "iana-registry")]
+    pub fn from_iana_xml<R: std::io::Read>(mut reader: R) -> Result<Self, Error> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        let mut warnings = Vec::new();
+        let parsed = crate::iana::parse_xml(&contents, &mut warnings)?;
+        Ok(Self::from_parsed(parsed, warnings))
+    }
+
+    #[cfg(feature = "iana-registry")]
+    fn from_parsed(parsed: crate::iana::Parsed, warnings: Vec<ParseWarning>) -> Self {
+        let mut registry = Self::default();
+        for record in parsed.records {
+            registry.insert(record);
+        }
+        registry.updated = parsed.updated;
+        #[cfg(feature = "optional-info")]
+        {
+            registry.people = parsed.people;
+        }
+        registry.warnings = warnings;
+        registry
+    }
+
+    /// Parse services from a string in `/etc/services` format
+    pub fn parse(contents: &str) -> Self {
+        // Reading from a string can't fail
+        Self::from_reader(contents.as_bytes()).unwrap_or_default()
+    }
+
+    /// Add a record to the registry
+    pub fn insert(&mut self, record: OwnedServiceRecord) {
+        let idx = self.records.len();
+
+        if record.is_range() {
+            self.ranges.push(idx);
+        } else {
+            self.by_port.entry(record.port).or_default().push(idx);
+        }
+        #[cfg(feature = "lookup-by-name")]
+        self.sorted_names.take();
+        #[cfg(feature = "lookup-by-name")]
+        for name in std::iter::once(&record.name).chain(&record.aliases) {
+            // Reserved records have no name, and aren't indexed under "" (like in build.rs)
+            if name.is_empty() {
+                continue;
+            }
+            let indices = self.by_name.entry(name.to_ascii_lowercase()).or_default();
+            // A record may list its own name (or the same alias) more than once
+            if indices.last() != Some(&idx) {
+                indices.push(idx);
+            }
+        }
+        #[cfg(feature = "optional-info")]
+        for reference in record.references() {
+            if let crate::Reference::Rfc(number) = reference {
+                let indices = self.by_rfc.entry(number).or_default();
+                if indices.last() != Some(&idx) {
+                    indices.push(idx);
+                }
+            }
+        }
+        #[cfg(feature = "optional-info")]
+        for (word, fields) in crate::text::record_words(&record) {
+            self.text_index.entry(word).or_default().push((idx, fields));
+        }
+
+        self.records.push(record);
+    }
+
+    /// Iterate over every record in the registry, in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &OwnedServiceRecord> {
+        self.records.iter()
+    }
+
+    /// Number of records in the registry
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether the registry has no records
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Date the registry was last updated
+    ///
+    /// Only known for registries loaded from the IANA XML format.
+    pub fn updated(&self) -> Option<Date> {
+        self.updated
+    }
+
+    /// Look up a person referenced by the registry
+    ///
+    /// See [`lookup_person`](crate::lookup_person).
+    #[cfg(feature = "optional-info")]
+    pub fn person(&self, id: &str) -> Option<Person> {
+        let id = crate::person::person_id(id);
+        self.people
+            .binary_search_by(|person| person.id.as_str().cmp(id))
+            .ok()
+            .map(|idx| self.people[idx].clone())
+    }
+
+    /// Malformed lines that were skipped while loading the registry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let registry = ServiceRegistry::parse("ssh 22/tcp\nbroken 22/tpc\n");
+    /// assert_eq!(registry.len(), 1);
+    /// assert_eq!(
+    ///     registry.warnings()[0].to_string(),
+    ///     "line 2: unknown transport protocol `tpc`"
+    /// );
+    /// ```
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    #[cfg(not(feature = "embed"))]
+    pub(crate) fn records(&self) -> &[OwnedServiceRecord] {
+        &self.records
+    }
+
+    /// Iterate over the records on ports within a range
+    ///
+    /// See [`services_in_range`](crate::services_in_range).
+    pub fn services_in_range<R: RangeBounds<u16>>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = &OwnedServiceRecord> {
+        let bounds = crate::port_bounds(range);
+        self.records
+            .iter()
+            .filter(move |record| bounds.is_some_and(|(start, end)| record.overlaps(start, end)))
+    }
+
+    /// Look up services by port number
+    ///
+    /// See [`lookup_by_port`](crate::lookup_by_port).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// // /etc/services has no ranges, so widen a record into one
+    /// let mut x11 = ServiceRegistry::parse("x11 6000/tcp\n").iter().next().unwrap().clone();
+    /// x11.port_end = 6063;
+    /// let mut registry = ServiceRegistry::parse("ssh 22/tcp\n");
+    /// registry.insert(x11);
+    ///
+    /// for port in [6000, 6010, 6063] {
+    ///     let services = registry.lookup_by_port(port).unwrap();
+    ///     assert_eq!(services.len(), 1);
+    ///     assert_eq!((services[0].name.as_str(), services[0].port_end), ("x11", 6063));
+    /// }
+    /// assert_eq!(registry.lookup_by_port(6064), None);
+    /// assert_eq!(registry.lookup_by_port(22).unwrap()[0].name, "ssh");
+    /// ```
+    pub fn lookup_by_port(&self, port: u16) -> Option<Vec<OwnedServiceRecord>> {
+        self.collect(self.port_indices(port))
+    }
+
+    /// Look up services by service name
+    ///
+    /// See [`lookup_by_name`](crate::lookup_by_name).
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_by_name(&self, name: &str) -> Option<Vec<OwnedServiceRecord>> {
+        self.collect(
+            self.by_name
+                .get(&name.to_ascii_lowercase())?
+                .iter()
+                .copied(),
+        )
+    }
+
+    /// Search service names and aliases, ranking prefix matches before substring and
+    /// fuzzy matches
+    ///
+    /// See [`search_names`](crate::search_names).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// let registry = ServiceRegistry::parse(
+    ///     "kerberos 88/tcp krb5\nkerberos-adm 749/tcp\nKerberos_Master 751/tcp\n\
+    ///      kerbside 1/tcp\nx-kerb 2/tcp\nekerb 3/tcp\nkern 4/tcp\nherb 5/tcp\nkarp 6/tcp\n\
+    ///      postgres 5432/tcp\npostgresql 5433/tcp\n",
+    /// );
+    ///
+    /// // Prefix matches (shortest first, then alphabetically), then substring matches
+    /// // (earliest first), then names one edit away (alphabetically)
+    /// assert_eq!(
+    ///     registry.search_names("KERB"),
+    ///     [
+    ///         "kerberos",
+    ///         "kerbside",
+    ///         "kerberos-adm",
+    ///         "Kerberos_Master",
+    ///         "ekerb",
+    ///         "x-kerb",
+    ///         "herb",
+    ///         "kern",
+    ///     ]
+    /// );
+    ///
+    /// // Longer queries allow two edits, but not names much longer than the query
+    /// assert_eq!(registry.search_names("postgrs"), ["postgres"]);
+    /// assert_eq!(registry.search_names("krb"), ["krb5"]);
+    /// assert!(registry.search_names(" ").is_empty());
+    /// ```
+    #[cfg(feature = "lookup-by-name")]
+    pub fn search_names(&self, query: &str) -> Vec<&str> {
+        let names = self.sorted_names.get_or_init(|| {
+            let mut names: Vec<(String, String)> = self
+                .by_name
+                .iter()
+                .map(|(key, indices)| {
+                    let record = &self.records[indices[0]];
+                    let spelling = std::iter::once(&record.name)
+                        .chain(&record.aliases)
+                        .find(|name| name.eq_ignore_ascii_case(key))
+                        .unwrap_or(key);
+                    (key.clone(), spelling.clone())
+                })
+                .collect();
+            names.sort_unstable();
+            names
+        });
+
+        crate::search::search(query, names)
+    }
+
+    /// Look up services by service name, matching its spelling exactly
+    ///
+    /// See [`lookup_by_name_strict`](crate::lookup_by_name_strict).
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_by_name_strict(&self, name: &str) -> Option<Vec<OwnedServiceRecord>> {
+        self.collect(
+            self.by_name
+                .get(&name.to_ascii_lowercase())?
+                .iter()
+                .copied()
+                .filter(|&idx| {
+                    let record = &self.records[idx];
+                    record.name == name || record.aliases.iter().any(|alias| alias == name)
+                }),
+        )
+    }
+
+    /// Look up the services whose registration references an RFC
+    ///
+    /// See [`lookup_by_rfc`](crate::lookup_by_rfc).
+    #[cfg(feature = "optional-info")]
+    pub fn lookup_by_rfc(&self, number: u32) -> Option<Vec<OwnedServiceRecord>> {
+        self.collect(self.by_rfc.get(&number)?.iter().copied())
+    }
+
+    /// Report the records whose service name breaks the rules of RFC 6335 §5.1
+    ///
+    /// Reserved records without a name are skipped, and aliases aren't checked. See
+    /// [`validate_service_name`](crate::validate_service_name).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::{NameError, ServiceRegistry};
+    ///
+    /// let registry = ServiceRegistry::parse("ssh 22/tcp\nKerberos_Master 751/tcp\n");
+    /// let invalid: Vec<_> = registry.invalid_names().collect();
+    /// assert_eq!(invalid.len(), 1);
+    /// assert_eq!(invalid[0].0.name, "Kerberos_Master");
+    /// assert_eq!(invalid[0].1, NameError::InvalidCharacter { c: '_', position: 8 });
+    /// ```
+    pub fn invalid_names(&self) -> impl Iterator<Item = (&OwnedServiceRecord, NameError)> {
+        self.records.iter().filter_map(invalid_name)
+    }
+
+    /// Search the descriptions, assignees and notes of the records
+    ///
+    /// See [`search_text`](crate::search_text).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::ServiceRegistry;
+    ///
+    /// // The comment of a services file line becomes the description
+    /// let mut registry = ServiceRegistry::default();
+    /// for (line, assignee) in [
+    ///     ("cisco-sccp 2000/tcp # Cisco SCCP", Some("[Cisco_Systems]")),
+    ///     ("sccp-alt 2001/tcp # Cisco call control", None),
+    ///     ("fooproto 2002/tcp # Foo protocol", Some("[Cisco_Systems]")),
+    ///     ("ciscoworks 2003/tcp # Network management", None),
+    ///     ("bar 2004/tcp # Bar", None),
+    /// ] {
+    ///     let mut record = ServiceRegistry::parse(line).iter().next().unwrap().clone();
+    ///     record.assignee = assignee.map(str::to_string);
+    ///     registry.insert(record);
+    /// }
+    /// let names = |query| -> Vec<String> {
+    ///     registry.search_text(query).into_iter().map(|record| record.name).collect()
+    /// };
+    ///
+    /// // Name matches rank above description matches, which rank above assignee
+    /// // matches. Whole words count more than prefixes, and ties go by port.
+    /// assert_eq!(names("cisco"), ["cisco-sccp", "sccp-alt", "ciscoworks", "fooproto"]);
+    ///
+    /// // Every word has to match, but only the start of a word
+    /// assert_eq!(names("CISCO call"), ["sccp-alt"]);
+    /// assert_eq!(names("netw"), ["ciscoworks"]);
+    /// assert!(names("cisco telephony").is_empty());
+    /// assert!(names("isco").is_empty());
+    /// ```
+    #[cfg(feature = "optional-info")]
+    pub fn search_text(&self, query: &str) -> Vec<OwnedServiceRecord> {
+        let key = |idx: usize| {
+            let record = &self.records[idx];
+            let protocol = record.protocol.as_str();
+            (record.port, record.port_end, protocol, record.name.as_str())
+        };
+        let indices = crate::text::rank(query, key, |term| {
+            // The index is sorted by word, so the words starting with the term are
+            // contiguous
+            self.text_index
+                .range::<str, _>((Bound::Included(term), Bound::Unbounded))
+                .take_while(|(word, _)| word.starts_with(term))
+                .flat_map(|(word, postings)| {
+                    postings
+                        .iter()
+                        .map(move |&(idx, fields)| (idx, fields, word == term))
+                })
+                .collect()
+        });
+
+        indices
+            .into_iter()
+            .map(|idx| self.records[idx].clone())
+            .collect()
+    }
+
+    /// Look up services by port number and transport protocol
+    ///
+    /// See [`lookup`](crate::lookup).
+    pub fn lookup(
+        &self,
+        port: u16,
+        protocol: TransportProtocol,
+    ) -> Option<Vec<OwnedServiceRecord>> {
+        self.collect(
+            self.port_indices(port)
+                .filter(|&idx| self.records[idx].protocol == protocol),
+        )
+    }
+
+    /// Look up services by service name and transport protocol
+    ///
+    /// See [`lookup_name`](crate::lookup_name).
+    #[cfg(feature = "lookup-by-name")]
+    pub fn lookup_name(
+        &self,
+        name: &str,
+        protocol: TransportProtocol,
+    ) -> Option<Vec<OwnedServiceRecord>> {
+        let indices = self
+            .by_name
+            .get(&name.to_ascii_lowercase())?
+            .iter()
+            .copied();
+        self.collect(indices.filter(|&idx| self.records[idx].protocol == protocol))
+    }
+
+    fn port_indices(&self, port: u16) -> impl Iterator<Item = usize> + '_ {
+        let exact = self.by_port.get(&port).into_iter().flatten().copied();
+        let ranges = self
+            .ranges
+            .iter()
+            .copied()
+            .filter(move |&idx| self.records[idx].contains_port(port));
+
+        exact.chain(ranges)
+    }
+
+    fn collect<I: Iterator<Item = usize>>(&self, indices: I) -> Option<Vec<OwnedServiceRecord>> {
+        let results: Vec<_> = indices.map(|idx| self.records[idx].clone()).collect();
+
+        if results.is_empty() {
+            None
+        } else {
+            Some(results)
+        }
+    }
+}
+
+fn invalid_name(record: &OwnedServiceRecord) -> Option<(&OwnedServiceRecord, NameError)> {
+    if record.name.is_empty() {
+        return None;
+    }
+    crate::validate_service_name(&record.name)
+        .err()
+        .map(|error| (record, error))
+}
+
+fn new_record(
+    name: String,
+    port: u16,
+    protocol: TransportProtocol,
+    aliases: Vec<String>,
+    #[cfg_attr(not(feature = "optional-info"), allow(unused_variables))] description: String,
+) -> OwnedServiceRecord {
+    OwnedServiceRecord {
+        name,
+        port,
+        port_end: port,
+        protocol,
+        aliases,
+        #[cfg(feature = "optional-info")]
+        description,
+        #[cfg(feature = "optional-info")]
+        assignee: None,
+        #[cfg(feature = "optional-info")]
+        contact: None,
+        #[cfg(feature = "optional-info")]
+        registration_date: None,
+        #[cfg(feature = "optional-info")]
+        modification_date: None,
+        #[cfg(feature = "optional-info")]
+        reference: None,
+        #[cfg(feature = "optional-info")]
+        service_code: None,
+        #[cfg(feature = "optional-info")]
+        unauthorized_use: None,
+        #[cfg(feature = "optional-info")]
+        assignment_notes: None,
+    }
+}
+
+fn parse_services_file<R, F>(
+    reader: R,
+    warnings: &mut Vec<ParseWarning>,
+    mut callback: F,
+) -> Result<(), Error>
+where
+    R: BufRead,
+    F: FnMut(String, u16, TransportProtocol, Vec<String>, String) -> bool,
+{
+    for (idx, line) in reader.split(b'\n').enumerate() {
+        let line_number = idx + 1;
+        let line = line?;
+
+        let mut warn = |message: String| {
+            warnings.push(ParseWarning {
+                line: line_number,
+                message,
+            })
+        };
+
+        let Ok(line) = std::str::from_utf8(&line) else {
+            warn("line is not valid UTF-8".to_string());
+            continue;
+        };
+        let line = line.trim();
+
+        // Skip comments and empty lines
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Parse line format: service port/protocol [aliases] [#comment]
+        let (fields, comment) = match line.split_once('#') {
+            Some((fields, comment)) => (fields, comment.trim()),
+            None => (line, ""),
+        };
+        let parts: Vec<&str> = fields.split_whitespace().collect();
+        if parts.len() < 2 {
+            warn("expected a service name followed by port/protocol".to_string());
+            continue;
+        }
+
+        let name = parts[0].to_string();
+
+        // Parse port/protocol
+        let Some((port, protocol)) = parts[1].split_once('/') else {
+            warn(format!("expected port/protocol, found `{}`", parts[1]));
+            continue;
+        };
+
+        let port: u16 = match port.parse() {
+            Ok(p) => p,
+            Err(_) => {
+                warn(format!("invalid port `{}`", port));
+                continue;
+            }
+        };
+
+        let protocol: TransportProtocol = match protocol.parse() {
+            Ok(p) => p,
+            Err(e) => {
+                warn(e.to_string());
+                continue;
+            }
+        };
+
+        // Anything between the port/protocol and the comment is an alias
+        let aliases = parts[2..].iter().map(|alias| alias.to_string()).collect();
+        let description = comment.to_string();
+
+        // Call callback, if it returns false, stop iteration
+        if !callback(name, port, protocol, aliases, description) {
+            break;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+/// Rank the names matching a search query
+///
+/// `names` holds `(lowercased name, spelling)` pairs sorted by lowercased name. Names
+/// starting with the query come first (shortest first), then names containing it
+/// (earliest match first), then names within a small edit distance of it (closest
+/// first). Ties are broken alphabetically.
+pub(crate) fn search<'a, S: AsRef<str>>(query: &str, names: &'a [(S, S)]) -> Vec<&'a str> {
+    let query = query.trim().to_ascii_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let key = |idx: usize| names[idx].0.as_ref();
+
+    // The names are sorted, so the prefix matches are contiguous
+    let start = names.partition_point(|(name, _)| name.as_ref() < query.as_str());
+    let end = start + names[start..].partition_point(|(name, _)| name.as_ref().starts_with(&query));
+    let mut prefix: Vec<usize> = (start..end).collect();
+    prefix.sort_by_key(|&idx| key(idx).len());
+
+    let mut substring: Vec<(usize, usize)> = (0..names.len())
+        .filter(|idx| !(start..end).contains(idx))
+        .filter_map(|idx| Some((key(idx).find(&query)?, idx)))
+        .collect();
+    substring.sort();
+
+    let max_distance = match query.len() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    };
+    let mut fuzzy: Vec<(usize, usize)> = if max_distance == 0 {
+        Vec::new()
+    } else {
+        (0..names.len())
+            .filter(|&idx| !key(idx).contains(&query))
+            .filter_map(|idx| {
+                let distance = edit_distance(&query, key(idx), max_distance)?;
+                Some((distance, idx))
+            })
+            .collect()
+    };
+    fuzzy.sort();
+
+    prefix
+        .into_iter()
+        .chain(substring.into_iter().map(|(_, idx)| idx))
+        .chain(fuzzy.into_iter().map(|(_, idx)| idx))
+        .map(|idx| names[idx].1.as_ref())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, or `None` if it exceeds `max`
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, &ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        // Every later row is at least as large as this row's minimum
+        if current.iter().min().is_some_and(|&min| min > max) {
+            return None;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
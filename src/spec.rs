@@ -0,0 +1,227 @@
+use crate::{ServiceRecord, TransportProtocol};
+use std::fmt;
+use std::str::FromStr;
+
+impl TransportProtocol {
+    /// Lowercase protocol name, as used in `/etc/services` and the IANA registry
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TransportProtocol::Tcp => "tcp",
+            TransportProtocol::Udp => "udp",
+            TransportProtocol::Sctp => "sctp",
+            TransportProtocol::Dccp => "dccp",
+        }
+    }
+}
+
+impl fmt::Display for TransportProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TransportProtocol {
+    type Err = ParseProtocolError;
+
+    /// Parse a protocol name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tcp" => Ok(TransportProtocol::Tcp),
+            "udp" => Ok(TransportProtocol::Udp),
+            "sctp" => Ok(TransportProtocol::Sctp),
+            "dccp" => Ok(TransportProtocol::Dccp),
+            _ => Err(ParseProtocolError {
+                protocol: s.to_string(),
+            }),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown [`TransportProtocol`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseProtocolError {
+    protocol: String,
+}
+
+impl fmt::Display for ParseProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown transport protocol `{}`", self.protocol)
+    }
+}
+
+impl std::error::Error for ParseProtocolError {}
+
+/// A service given as `port/proto`, `name/proto`, or a bare port or name
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{ServiceSpec, TransportProtocol};
+///
+/// let spec: ServiceSpec = "443/tcp".parse().unwrap();
+/// assert_eq!(
+///     spec,
+///     ServiceSpec::Port { port: 443, protocol: Some(TransportProtocol::Tcp) }
+/// );
+/// assert_eq!(spec.to_string(), "443/tcp");
+///
+/// let err = "443/tpc".parse::<ServiceSpec>().unwrap_err();
+/// assert_eq!(err.to_string(), "unknown transport protocol `tpc`");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ServiceSpec {
+    /// A port number, optionally restricted to one protocol
+    Port {
+        /// Port number
+        port: u16,
+        /// Protocol after the `/`, if any
+        protocol: Option<TransportProtocol>,
+    },
+    /// A service name, optionally restricted to one protocol
+    Name {
+        /// Service name, as written
+        name: String,
+        /// Protocol after the `/`, if any
+        protocol: Option<TransportProtocol>,
+    },
+}
+
+impl ServiceSpec {
+    /// The protocol the spec is restricted to, if any
+    pub fn protocol(&self) -> Option<TransportProtocol> {
+        match self {
+            ServiceSpec::Port { protocol, .. } | ServiceSpec::Name { protocol, .. } => *protocol,
+        }
+    }
+
+    /// Look up the services matching this spec
+    ///
+    /// Ports are resolved with [`lookup_by_port`](crate::lookup_by_port) (or
+    /// [`lookup`](crate::lookup) when a protocol is given). Names are resolved the same
+    /// way through [`lookup_by_name`](crate::lookup_by_name), so they only match with the
+    /// `lookup-by-name` feature.
+    pub fn resolve(&self) -> Option<Vec<ServiceRecord>> {
+        match self {
+            ServiceSpec::Port {
+                port,
+                protocol: Some(protocol),
+            } => crate::lookup(*port, *protocol),
+            ServiceSpec::Port {
+                port,
+                protocol: None,
+            } => crate::lookup_by_port(*port),
+            #[cfg(feature = "lookup-by-name")]
+            ServiceSpec::Name {
+                name,
+                protocol: Some(protocol),
+            } => crate::lookup_name(name, *protocol),
+            #[cfg(feature = "lookup-by-name")]
+            ServiceSpec::Name {
+                name,
+                protocol: None,
+            } => crate::lookup_by_name(name),
+            #[cfg(not(feature = "lookup-by-name"))]
+            ServiceSpec::Name { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ServiceSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceSpec::Port { port, .. } => write!(f, "{}", port)?,
+            ServiceSpec::Name { name, .. } => f.write_str(name)?,
+        }
+
+        match self.protocol() {
+            Some(protocol) => write!(f, "/{}", protocol),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ServiceSpec {
+    type Err = ParseSpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseSpecError::Empty);
+        }
+
+        let (service, protocol) = match s.split_once('/') {
+            Some((service, protocol)) => (service, Some(protocol)),
+            None => (s, None),
+        };
+
+        let protocol = match protocol {
+            Some("") => return Err(ParseSpecError::MissingProtocol(service.to_string())),
+            Some(protocol) => Some(
+                protocol
+                    .parse()
+                    .map_err(|_| ParseSpecError::InvalidProtocol(protocol.to_string()))?,
+            ),
+            None => None,
+        };
+
+        if service.is_empty() {
+            return Err(ParseSpecError::MissingService(s.to_string()));
+        }
+
+        if service.bytes().all(|b| b.is_ascii_digit()) {
+            let port = service
+                .parse()
+                .map_err(|_| ParseSpecError::InvalidPort(service.to_string()))?;
+            Ok(ServiceSpec::Port { port, protocol })
+        } else if service.chars().any(char::is_whitespace) {
+            Err(ParseSpecError::InvalidName(service.to_string()))
+        } else {
+            Ok(ServiceSpec::Name {
+                name: service.to_string(),
+                protocol,
+            })
+        }
+    }
+}
+
+/// Error returned when parsing a [`ServiceSpec`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSpecError {
+    /// The spec was empty
+    Empty,
+    /// Nothing came before the `/`
+    MissingService(String),
+    /// Nothing came after the `/`
+    MissingProtocol(String),
+    /// The port part is numeric but out of range
+    InvalidPort(String),
+    /// The name part contains whitespace
+    InvalidName(String),
+    /// The protocol part isn't a known transport protocol
+    InvalidProtocol(String),
+}
+
+impl fmt::Display for ParseSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSpecError::Empty => write!(f, "empty service spec"),
+            ParseSpecError::MissingService(spec) => {
+                write!(f, "missing port or service name before `/` in `{}`", spec)
+            }
+            ParseSpecError::MissingProtocol(service) => {
+                write!(f, "missing protocol after `{}/`", service)
+            }
+            ParseSpecError::InvalidPort(port) => {
+                write!(f, "invalid port `{}`: must be at most {}", port, u16::MAX)
+            }
+            ParseSpecError::InvalidName(name) => {
+                write!(f, "invalid service name `{}`: contains whitespace", name)
+            }
+            ParseSpecError::InvalidProtocol(protocol) => {
+                write!(f, "unknown transport protocol `{}`", protocol)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSpecError {}
@@ -0,0 +1,248 @@
+use crate::{NameError, ServiceRecord, TransportProtocol};
+use std::fmt;
+use std::str::FromStr;
+
+impl TransportProtocol {
+    /// Protocol label used in DNS SRV owner names (`_tcp`, `_udp`, ...)
+    pub fn srv_label(self) -> &'static str {
+        match self {
+            TransportProtocol::Tcp => "_tcp",
+            TransportProtocol::Udp => "_udp",
+            TransportProtocol::Sctp => "_sctp",
+            TransportProtocol::Dccp => "_dccp",
+        }
+    }
+}
+
+impl ServiceRecord {
+    /// The `_service._proto` label of this service, as used in DNS SRV owner names
+    ///
+    /// Returns `None` for records whose name isn't a valid service name (see
+    /// [`validate_service_name`](crate::validate_service_name)), which includes records
+    /// without a name (e.g. reserved ports) and legacy names like `Kerberos_Master`.
+    /// The labels that are returned always parse back into the same [`SrvLabel`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::{all_services, lookup_by_port, SrvLabel};
+    ///
+    /// for service in lookup_by_port(389).into_iter().flatten() {
+    ///     if let Some(label) = service.srv_label() {
+    ///         println!("{}.example.com", label);
+    ///     }
+    /// }
+    ///
+    /// for service in all_services() {
+    ///     if let Some(label) = service.srv_label() {
+    ///         assert_eq!(label.to_string().parse::<SrvLabel>(), Ok(label));
+    ///     }
+    /// }
+    /// ```
+    pub fn srv_label(&self) -> Option<SrvLabel> {
+        #[cfg(feature = "embed")]
+        let name = self.name;
+        #[cfg(not(feature = "embed"))]
+        let name = self.name.as_str();
+
+        SrvLabel::new(name, self.protocol).ok()
+    }
+}
+
+/// A DNS SRV / DNS-SD service label like `_ldap._tcp`, optionally followed by a domain
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::{NameError, SrvLabel, SrvLabelError, TransportProtocol};
+///
+/// let label: SrvLabel = "_ldap._tcp.example.com".parse().unwrap();
+/// assert_eq!(label.service, "ldap");
+/// assert_eq!(label.protocol, TransportProtocol::Tcp);
+/// assert_eq!(label.domain.as_deref(), Some("example.com"));
+///
+/// // The service label has to be a valid service name
+/// assert_eq!(
+///     "_my--app._tcp".parse::<SrvLabel>(),
+///     Err(SrvLabelError::InvalidService(NameError::ConsecutiveHyphens { position: 2 }))
+/// );
+///
+/// let label = SrvLabel::new("ldap", TransportProtocol::Tcp)?.with_domain("example.com");
+/// assert_eq!(label.to_string(), "_ldap._tcp.example.com");
+///
+/// // Legacy registry names can't be used in labels
+/// assert_eq!(
+///     SrvLabel::new("Kerberos_Master", TransportProtocol::Tcp),
+///     Err(NameError::InvalidCharacter { c: '_', position: 8 })
+/// );
+/// # Ok::<(), NameError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SrvLabel {
+    /// Service name, without the leading underscore
+    pub service: String,
+    /// Transport protocol
+    pub protocol: TransportProtocol,
+    /// Domain following the protocol label, if any (without the trailing dot)
+    pub domain: Option<String>,
+}
+
+impl SrvLabel {
+    /// Build the label for a service over a transport protocol
+    ///
+    /// Fails if `service` isn't a valid service name, so that every label can be
+    /// parsed back with [`FromStr`].
+    pub fn new(service: &str, protocol: TransportProtocol) -> Result<Self, NameError> {
+        crate::validate_service_name(service)?;
+
+        Ok(Self {
+            service: service.to_string(),
+            protocol,
+            domain: None,
+        })
+    }
+
+    /// Append a domain to the label
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        let domain = domain.trim_matches('.');
+        self.domain = (!domain.is_empty()).then(|| domain.to_string());
+        self
+    }
+
+    /// Look up the registered services the label refers to
+    ///
+    /// The service name is resolved through [`lookup_name`](crate::lookup_name), so
+    /// case doesn't matter. Labels naming a service that isn't registered over the
+    /// label's protocol return [`SrvLabelError::Unregistered`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iana_services::{SrvLabel, SrvLabelError};
+    ///
+    /// let label: SrvLabel = "_not-a-service._tcp.example.com".parse().unwrap();
+    /// assert!(matches!(label.resolve(), Err(SrvLabelError::Unregistered { .. })));
+    /// ```
+    #[cfg(feature = "lookup-by-name")]
+    pub fn resolve(&self) -> Result<Vec<ServiceRecord>, SrvLabelError> {
+        crate::lookup_name(&self.service, self.protocol).ok_or_else(|| {
+            SrvLabelError::Unregistered {
+                service: self.service.clone(),
+                protocol: self.protocol,
+            }
+        })
+    }
+}
+
+/// Parse a `_service._proto[.domain]` label and look up the services it refers to
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::resolve_srv_label;
+///
+/// match resolve_srv_label("_ldap._tcp.example.com") {
+///     Ok(services) => println!("LDAP uses port {}", services[0].port),
+///     Err(e) => eprintln!("{e}"),
+/// }
+/// ```
+#[cfg(feature = "lookup-by-name")]
+pub fn resolve_srv_label(label: &str) -> Result<Vec<ServiceRecord>, SrvLabelError> {
+    label.parse::<SrvLabel>()?.resolve()
+}
+
+impl fmt::Display for SrvLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "_{}.{}", self.service, self.protocol.srv_label())?;
+
+        match &self.domain {
+            Some(domain) => write!(f, ".{}", domain),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for SrvLabel {
+    type Err = SrvLabelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches('.');
+        let mut labels = s.splitn(3, '.');
+
+        let service = labels.next().unwrap_or("");
+        let service = service
+            .strip_prefix('_')
+            .ok_or_else(|| SrvLabelError::MissingUnderscore(service.to_string()))?;
+        if service.is_empty() {
+            return Err(SrvLabelError::MissingService(s.to_string()));
+        }
+
+        let protocol = labels
+            .next()
+            .ok_or_else(|| SrvLabelError::MissingProtocol(s.to_string()))?;
+        let protocol = protocol
+            .strip_prefix('_')
+            .ok_or_else(|| SrvLabelError::MissingUnderscore(protocol.to_string()))?
+            .parse()
+            .map_err(|_| SrvLabelError::InvalidProtocol(protocol.to_string()))?;
+
+        let label = Self::new(service, protocol).map_err(SrvLabelError::InvalidService)?;
+        Ok(label.with_domain(labels.next().unwrap_or("")))
+    }
+}
+
+/// Error returned when parsing or resolving a [`SrvLabel`] fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SrvLabelError {
+    /// The service or protocol label doesn't start with `_`
+    MissingUnderscore(String),
+    /// The service label is just `_`
+    MissingService(String),
+    /// The service label isn't a valid service name
+    InvalidService(NameError),
+    /// Nothing follows the service label
+    MissingProtocol(String),
+    /// The protocol label isn't a known transport protocol
+    InvalidProtocol(String),
+    /// The label names a service that isn't registered over its protocol
+    Unregistered {
+        /// Service name from the label
+        service: String,
+        /// Protocol from the label
+        protocol: TransportProtocol,
+    },
+}
+
+impl fmt::Display for SrvLabelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SrvLabelError::MissingUnderscore(label) => {
+                write!(f, "label `{}` must start with `_`", label)
+            }
+            SrvLabelError::MissingService(label) => {
+                write!(f, "missing service name in `{}`", label)
+            }
+            SrvLabelError::InvalidService(e) => write!(f, "invalid service label: {}", e),
+            SrvLabelError::MissingProtocol(label) => {
+                write!(f, "missing protocol label after `{}`", label)
+            }
+            SrvLabelError::InvalidProtocol(label) => {
+                write!(f, "unknown transport protocol label `{}`", label)
+            }
+            SrvLabelError::Unregistered { service, protocol } => write!(
+                f,
+                "service `{}` isn't registered over {}",
+                service, protocol
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SrvLabelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SrvLabelError::InvalidService(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::text_tokens::{FIELD_ASSIGNEE, FIELD_DESCRIPTION, FIELD_NAME, FIELD_NOTES, tokens};
+use crate::{OwnedServiceRecord, ServiceRecord};
+use std::collections::HashMap;
+
+/// Score of a query term found in the given fields: a match in the name counts more
+/// than one in the description, which counts more than the assignee or the notes.
+/// Whole-word matches count twice as much as prefix matches.
+fn term_score(fields: u8, exact: bool) -> u32 {
+    let weight = if fields & FIELD_NAME != 0 {
+        8
+    } else if fields & FIELD_DESCRIPTION != 0 {
+        4
+    } else if fields & FIELD_ASSIGNEE != 0 {
+        2
+    } else if fields & FIELD_NOTES != 0 {
+        1
+    } else {
+        0
+    };
+
+    if exact { weight * 2 } else { weight }
+}
+
+/// Rank records for a query, given a way to find each term's matches
+///
+/// `matches(term)` yields `(record index, fields, exact)` for every word starting
+/// with `term`. Records must match every term; they're ranked by total score, then
+/// by `key(record index)` (port, port end, protocol and name), so both backends
+/// return the same order.
+pub(crate) fn rank<'a, K, F>(query: &str, key: K, mut matches: F) -> Vec<usize>
+where
+    K: Fn(usize) -> (u16, u16, &'static str, &'a str),
+    F: FnMut(&str) -> Vec<(usize, u8, bool)>,
+{
+    let terms: Vec<String> = tokens(query).collect();
+    let mut scores: HashMap<usize, u32> = HashMap::new();
+
+    for (i, term) in terms.iter().enumerate() {
+        let mut term_scores: HashMap<usize, u32> = HashMap::new();
+        for (idx, fields, exact) in matches(term) {
+            let score = term_scores.entry(idx).or_default();
+            *score = (*score).max(term_score(fields, exact));
+        }
+
+        if i == 0 {
+            scores = term_scores;
+        } else {
+            scores.retain(|idx, _| term_scores.contains_key(idx));
+            for (idx, score) in &mut scores {
+                *score += term_scores[idx];
+            }
+        }
+    }
+
+    let mut ranked: Vec<(usize, u32)> = scores.into_iter().collect();
+    ranked.sort_by(|(a, a_score), (b, b_score)| {
+        b_score.cmp(a_score).then_with(|| key(*a).cmp(&key(*b)))
+    });
+    ranked.into_iter().map(|(idx, _)| idx).collect()
+}
+
+/// The words of a record's searchable fields, with the fields each word appears in
+pub(crate) fn record_words(record: &OwnedServiceRecord) -> HashMap<String, u8> {
+    crate::text_tokens::record_words(
+        &record.name,
+        &record.description,
+        record.assignee.as_deref(),
+        record.assignment_notes.as_deref(),
+    )
+}
+
+/// Search the descriptions, assignees and notes of all services
+///
+/// The query is split into words, and every word has to match the start of a word in
+/// the record's name, description, assignee or assignment notes (ignoring case).
+/// Records are ranked by where the words matched (name, then description, then
+/// assignee and notes) and whether they matched whole words. With the `embed`
+/// feature, this uses an inverted index generated at build time.
+///
+/// # Examples
+///
+/// ```
+/// use iana_services::search_text;
+///
+/// // Which ports does a vendor use?
+/// for service in search_text("cisco").into_iter().take(10) {
+///     println!("{}/{}: {}", service.port, service.protocol, service.description);
+/// }
+///
+/// // The generated index ranks records the same way as a registry loaded at runtime
+/// #[cfg(feature = "embed")]
+/// {
+///     let registry = iana_services::ServiceRegistry::embedded();
+///     for query in ["http", "secure shell", "kerb"] {
+///         let embedded: Vec<_> = search_text(query)
+///             .into_iter()
+///             .map(|s| (s.port, s.protocol, s.name.to_string()))
+///             .collect();
+///         let loaded: Vec<_> = registry
+///             .search_text(query)
+///             .into_iter()
+///             .map(|s| (s.port, s.protocol, s.name))
+///             .collect();
+///         assert!(!embedded.is_empty());
+///         assert_eq!(embedded, loaded);
+///     }
+/// }
+/// ```
+pub fn search_text(query: &str) -> Vec<ServiceRecord> {
+    #[cfg(feature = "embed")]
+    return crate::embedded::search_text_impl(query);
+
+    #[cfg(not(feature = "embed"))]
+    return crate::runtime::registry().search_text(query);
+}
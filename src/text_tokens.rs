@@ -0,0 +1,44 @@
+//! Splitting records into the words of the text index
+//!
+//! This file is shared by build.rs (through `#[path]`) and `search_text`, so the
+//! index generated for `embed` holds the same words as the one built at runtime. It
+//! can't use anything else from the crate.
+
+use std::collections::HashMap;
+
+/// Field flags of the words in the text index
+pub(crate) const FIELD_NAME: u8 = 1;
+pub(crate) const FIELD_DESCRIPTION: u8 = 2;
+pub(crate) const FIELD_ASSIGNEE: u8 = 4;
+pub(crate) const FIELD_NOTES: u8 = 8;
+
+/// Split text into lowercase words
+pub(crate) fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| token.len() >= 2)
+        .map(str::to_ascii_lowercase)
+}
+
+/// The words of a record's searchable fields, with the fields each word appears in
+pub(crate) fn record_words(
+    name: &str,
+    description: &str,
+    assignee: Option<&str>,
+    assignment_notes: Option<&str>,
+) -> HashMap<String, u8> {
+    let mut words: HashMap<String, u8> = HashMap::new();
+    let fields = [
+        (Some(name), FIELD_NAME),
+        (Some(description), FIELD_DESCRIPTION),
+        (assignee, FIELD_ASSIGNEE),
+        (assignment_notes, FIELD_NOTES),
+    ];
+
+    for (text, field) in fields {
+        for token in tokens(text.unwrap_or("")) {
+            *words.entry(token).or_default() |= field;
+        }
+    }
+
+    words
+}